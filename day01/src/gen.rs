@@ -0,0 +1,28 @@
+//! Random inputs in the shape of the puzzle input.
+
+use std::fmt::Write;
+
+use rand::Rng;
+
+/// Number of lines in the real puzzle input.
+pub const DEFAULT_SIZE: usize = 1000;
+
+/// Generates two lists of `size` five digit location ids.
+///
+/// Ids are drawn from a small range so that the right list contains repeats of
+/// the left one, as part 2 expects.
+pub fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+    let ids: Vec<u32> = (0..size.max(1))
+        .map(|_| rng.gen_range(10000..100000))
+        .collect();
+    let mut out = String::new();
+    for _ in 0..size {
+        let a = ids[rng.gen_range(0..ids.len())];
+        let b = match rng.gen_bool(0.5) {
+            true => ids[rng.gen_range(0..ids.len())],
+            false => rng.gen_range(10000..100000),
+        };
+        writeln!(out, "{a}   {b}").unwrap();
+    }
+    out
+}
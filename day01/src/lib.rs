@@ -1,34 +1,36 @@
+pub mod gen;
+
 use std::collections::HashMap;
 
 use aoc_traits::AdventOfCodeDay;
 use itertools::izip;
+use parsing::{error_at, parse_at};
 
 #[derive(Default)]
 pub struct Solver;
+impl Solver {
+    pub fn try_parse_input(input: &str) -> Result<(Vec<u32>, Vec<u32>), eyre::Report> {
+        let mut a = Vec::new();
+        let mut b = Vec::new();
+        for line in input.lines() {
+            let mut it = line.split_whitespace();
+            let (Some(first), Some(second), None) = (it.next(), it.next(), it.next()) else {
+                return Err(error_at(input, line, "expected two numbers"));
+            };
+            a.push(parse_at(input, first)?);
+            b.push(parse_at(input, second)?);
+        }
+        Ok((a, b))
+    }
+}
+
 impl AdventOfCodeDay for Solver {
     type ParsedInput<'a> = (Vec<u32>, Vec<u32>);
     type Part1Output = u32;
     type Part2Output = u32;
 
     fn parse_input(input: &str) -> Self::ParsedInput<'_> {
-        let mut a = Vec::new();
-        let mut b = Vec::new();
-        for line in input.lines() {
-            let mut it = line.split_whitespace();
-            a.push(
-                it.next()
-                    .unwrap()
-                    .parse()
-                    .expect("Could not parse first number"),
-            );
-            b.push(
-                it.next()
-                    .unwrap()
-                    .parse()
-                    .expect("Could not parse second number"),
-            );
-        }
-        (a, b)
+        Self::try_parse_input(input).expect("Failed to parse input")
     }
 
     fn solve_part1(input: &Self::ParsedInput<'_>) -> Self::Part1Output {
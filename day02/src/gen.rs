@@ -0,0 +1,33 @@
+//! Random inputs in the shape of the puzzle input.
+
+use std::fmt::Write;
+
+use itertools::Itertools;
+use rand::Rng;
+
+/// Number of reports in the real puzzle input.
+pub const DEFAULT_SIZE: usize = 1000;
+
+/// Generates `size` reports of five to eight levels.
+///
+/// Most reports are monotonic with small steps, some of them with a single bad
+/// level, so that all of safe, dampened and unsafe reports show up.
+pub fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+    let mut out = String::new();
+    for _ in 0..size {
+        let len = rng.gen_range(5..=8);
+        let sign = if rng.gen_bool(0.5) { 1 } else { -1 };
+        let mut level: i32 = rng.gen_range(20..80);
+        let mut levels = Vec::with_capacity(len);
+        for _ in 0..len {
+            levels.push(level);
+            level += sign * rng.gen_range(1..=3);
+        }
+        if rng.gen_bool(0.5) {
+            let idx = rng.gen_range(0..len);
+            levels[idx] += rng.gen_range(-5..=5);
+        }
+        writeln!(out, "{}", levels.iter().map(|l| l.max(&1)).join(" ")).unwrap();
+    }
+    out
+}
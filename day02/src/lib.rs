@@ -1,4 +1,7 @@
+pub mod gen;
+
 use aoc_traits::AdventOfCodeDay;
+use parsing::parse_at;
 
 fn sequence_ok(seq: &[u32]) -> bool {
     if seq.len() <= 1 {
@@ -24,21 +27,27 @@ fn subsequence_ok(seq: &[u32]) -> bool {
 
 #[derive(Default)]
 pub struct Solver;
-impl AdventOfCodeDay for Solver {
-    type ParsedInput<'a> = Vec<Vec<u32>>;
-    type Part1Output = usize;
-    type Part2Output = usize;
-
-    fn parse_input(input: &str) -> Self::ParsedInput<'_> {
+impl Solver {
+    pub fn try_parse_input(input: &str) -> Result<Vec<Vec<u32>>, eyre::Report> {
         input
             .lines()
             .map(|line| {
                 line.split_whitespace()
-                    .map(|x| x.parse().unwrap())
+                    .map(|x| parse_at(input, x))
                     .collect()
             })
             .collect()
     }
+}
+
+impl AdventOfCodeDay for Solver {
+    type ParsedInput<'a> = Vec<Vec<u32>>;
+    type Part1Output = usize;
+    type Part2Output = usize;
+
+    fn parse_input(input: &str) -> Self::ParsedInput<'_> {
+        Self::try_parse_input(input).expect("Failed to parse input")
+    }
 
     fn solve_part1(input: &Self::ParsedInput<'_>) -> Self::Part1Output {
         input.iter().filter(|x| sequence_ok(x)).count()
@@ -0,0 +1,52 @@
+//! Random inputs in the shape of the puzzle input.
+
+use rand::{seq::SliceRandom, Rng};
+
+/// Number of instructions in the real puzzle input, roughly.
+pub const DEFAULT_SIZE: usize = 800;
+
+/// Fragments that look like instructions but are not.
+const NOISE: &[&str] = &[
+    "mul[3,7]",
+    "mul(4*",
+    "mul ( 2 , 4 )",
+    "do_not",
+    "don't",
+    "?(12,34)",
+    "mul(1234,5)",
+    "from()",
+    "select()",
+    "when()",
+    "who()",
+    "#",
+    "%",
+    "&",
+    "'",
+    "+",
+    "<",
+    ">",
+    "[",
+    "]",
+    "^",
+];
+
+/// Generates corrupted memory with `size` instructions hidden between noise.
+pub fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+    let mut out = String::new();
+    for _ in 0..size {
+        for _ in 0..rng.gen_range(0..4) {
+            out.push_str(NOISE.choose(rng).unwrap());
+        }
+        match rng.gen_range(0..10) {
+            0 => out.push_str("do()"),
+            1 => out.push_str("don't()"),
+            _ => {
+                let a = rng.gen_range(1..1000);
+                let b = rng.gen_range(1..1000);
+                out.push_str(&format!("mul({a},{b})"));
+            }
+        }
+    }
+    out.push('\n');
+    out
+}
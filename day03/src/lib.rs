@@ -1,3 +1,5 @@
+pub mod gen;
+
 use aoc_traits::AdventOfCodeDay;
 use regex::Regex;
 
@@ -50,13 +52,11 @@ impl Executor for Phase2Executor {
 
 #[derive(Default)]
 pub struct Solver;
-impl AdventOfCodeDay for Solver {
-    type ParsedInput<'a> = Vec<Instruction>;
-    type Part1Output = u32;
-    type Part2Output = u32;
-
-    fn parse_input(input: &str) -> Self::ParsedInput<'_> {
-        let regex_mul = Regex::new("mul\\((\\d{1,3}),(\\d{1,3})\\)").unwrap();
+impl Solver {
+    /// Never fails, anything that is not an instruction is ignored by design.
+    pub fn try_parse_input(input: &str) -> Result<Vec<Instruction>, eyre::Report> {
+        // `\d` would also match digits of other scripts, which `parse` rejects
+        let regex_mul = Regex::new("mul\\(([0-9]{1,3}),([0-9]{1,3})\\)").unwrap();
         let regex_dont = Regex::new("don't\\(\\)").unwrap();
         let regex_do = Regex::new("do\\(\\)").unwrap();
         let dos = regex_do
@@ -72,7 +72,17 @@ impl AdventOfCodeDay for Solver {
         });
         let mut instructions = dos.chain(donts).chain(muls).collect::<Vec<_>>();
         instructions.sort_by_key(|x| x.0);
-        instructions.into_iter().map(|x| x.1).collect()
+        Ok(instructions.into_iter().map(|x| x.1).collect())
+    }
+}
+
+impl AdventOfCodeDay for Solver {
+    type ParsedInput<'a> = Vec<Instruction>;
+    type Part1Output = u32;
+    type Part2Output = u32;
+
+    fn parse_input(input: &str) -> Self::ParsedInput<'_> {
+        Self::try_parse_input(input).expect("Failed to parse input")
     }
 
     fn solve_part1(input: &Self::ParsedInput<'_>) -> Self::Part1Output {
@@ -103,4 +113,9 @@ mod tests {
         let parsed = Solver::parse_input(TEST_INPUT2);
         assert_eq!(Solver::solve_part2(&parsed), 48);
     }
+    #[test]
+    fn test_non_ascii_digits() {
+        let parsed = Solver::try_parse_input("mul(\u{663},4)mul(2,3)mul(\u{ff11},1)").unwrap();
+        assert_eq!(Solver::solve_part1(&parsed), 6);
+    }
 }
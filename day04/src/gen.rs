@@ -0,0 +1,28 @@
+//! Random inputs in the shape of the puzzle input.
+
+use grid::{Grid, OFFSETS8};
+use rand::{seq::SliceRandom, Rng};
+
+/// Side length of the real puzzle input.
+pub const DEFAULT_SIZE: usize = 140;
+
+/// Generates a `size` by `size` letter grid with hidden `XMAS` words.
+pub fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+    let size = size.max(1);
+    let cells = (0..size * size)
+        .map(|_| *b"XMAS".choose(rng).unwrap())
+        .collect();
+    let mut grid = Grid::from_vec(size, size, cells).unwrap();
+    for _ in 0..size * size / 20 {
+        let mut pos = (rng.gen_range(0..size), rng.gen_range(0..size));
+        let offset = *OFFSETS8.choose(rng).unwrap();
+        for &c in b"XMAS" {
+            grid[pos] = c;
+            match grid.offset(pos, offset) {
+                Some(next) => pos = next,
+                None => break,
+            }
+        }
+    }
+    grid.to_string()
+}
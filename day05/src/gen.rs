@@ -0,0 +1,37 @@
+//! Random inputs in the shape of the puzzle input.
+
+use std::fmt::Write;
+
+use itertools::Itertools;
+use rand::{seq::SliceRandom, Rng};
+
+/// Number of updates in the real puzzle input.
+pub const DEFAULT_SIZE: usize = 200;
+
+/// Generates ordering rules and `size` updates.
+///
+/// The rules form a total order of a random set of pages, so every update has
+/// exactly one correct ordering. About half of the updates are already in it.
+pub fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+    let mut pages: Vec<u8> = (10..100).collect();
+    pages.shuffle(rng);
+    pages.truncate(49);
+
+    let mut rules: Vec<(u8, u8)> = pages.iter().copied().tuple_combinations().collect();
+    rules.shuffle(rng);
+    let mut out = String::new();
+    for (a, b) in rules {
+        writeln!(out, "{a}|{b}").unwrap();
+    }
+    out.push('\n');
+
+    for _ in 0..size {
+        let len = rng.gen_range(2..=11) * 2 + 1;
+        let mut update: Vec<usize> = rand::seq::index::sample(rng, pages.len(), len).into_vec();
+        if rng.gen_bool(0.5) {
+            update.sort_unstable();
+        }
+        writeln!(out, "{}", update.iter().map(|&i| pages[i]).join(",")).unwrap();
+    }
+    out
+}
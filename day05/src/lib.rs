@@ -1,6 +1,11 @@
+pub mod gen;
+#[cfg(feature = "reference")]
+pub mod reference;
+
 use std::str::FromStr;
 
 use aoc_traits::AdventOfCodeDay;
+use parsing::{error_at, parse_at};
 
 #[derive(Debug, Clone)]
 pub struct PageGame {
@@ -20,14 +25,18 @@ impl FromStr for PageGame {
             .map(|line| {
                 let (a, b) = line
                     .split_once('|')
-                    .ok_or_else(|| eyre::eyre!("Invalid rule"))?;
-                Ok((a.parse()?, b.parse()?))
+                    .ok_or_else(|| error_at(s, line, "Invalid rule, expected `a|b`"))?;
+                Ok((parse_at(s, a)?, parse_at(s, b)?))
             })
             .collect::<Result<Vec<(u8, u8)>, eyre::Report>>()?;
 
         let pages = pages
             .lines()
-            .map(|line| line.split(',').map(|n| n.parse()).collect::<Result<_, _>>())
+            .map(|line| {
+                line.split(',')
+                    .map(|n| parse_at(s, n))
+                    .collect::<Result<_, _>>()
+            })
             .collect::<Result<_, _>>()?;
 
         let mut invalid_after: Vec<Vec<u8>> = vec![vec![]; 256];
@@ -98,13 +107,19 @@ impl PageGame {
 
 #[derive(Default)]
 pub struct Solver;
+impl Solver {
+    pub fn try_parse_input(input: &str) -> Result<PageGame, eyre::Report> {
+        input.parse()
+    }
+}
+
 impl AdventOfCodeDay for Solver {
     type ParsedInput<'a> = PageGame;
     type Part1Output = usize;
     type Part2Output = usize;
 
     fn parse_input(input: &str) -> Self::ParsedInput<'_> {
-        input.parse().expect("Failed to parse input")
+        Self::try_parse_input(input).expect("Failed to parse input")
     }
 
     fn solve_part1(input: &Self::ParsedInput<'_>) -> Self::Part1Output {
@@ -159,4 +174,11 @@ mod tests {
         let parsed = Solver::parse_input(TEST_INPUT);
         assert_eq!(Solver::solve_part2(&parsed), 123);
     }
+    #[test]
+    fn test_parse_errors() {
+        let err = Solver::try_parse_input("47|53\n97-13\n\n75,47\n").unwrap_err();
+        assert!(err.to_string().starts_with("line 2, column 1: "));
+        let err = Solver::try_parse_input("47|53\n\n75,47\n61,x3\n").unwrap_err();
+        assert!(err.to_string().starts_with("line 4, column 4: "));
+    }
 }
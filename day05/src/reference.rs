@@ -0,0 +1,77 @@
+//! Slow but obviously correct solutions to check [`crate::Solver`] against.
+
+use std::collections::HashSet;
+
+fn parse(input: &str) -> (HashSet<(u8, u8)>, Vec<Vec<u8>>) {
+    let (rules, updates) = input.split_once("\n\n").unwrap();
+    let rules = rules
+        .lines()
+        .map(|line| {
+            let (a, b) = line.split_once('|').unwrap();
+            (a.parse().unwrap(), b.parse().unwrap())
+        })
+        .collect();
+    let updates = updates
+        .lines()
+        .map(|line| line.split(',').map(|n| n.parse().unwrap()).collect())
+        .collect();
+    (rules, updates)
+}
+
+/// Whether no rule requires a later page to come before an earlier one.
+fn in_order(rules: &HashSet<(u8, u8)>, update: &[u8]) -> bool {
+    (0..update.len())
+        .all(|i| (i + 1..update.len()).all(|j| !rules.contains(&(update[j], update[i]))))
+}
+
+pub fn solve_part1(input: &str) -> usize {
+    let (rules, updates) = parse(input);
+    updates
+        .iter()
+        .filter(|update| in_order(&rules, update))
+        .map(|update| update[update.len() / 2] as usize)
+        .sum()
+}
+
+/// Reorders the updates by repeatedly taking a page that no remaining page
+/// has to come before.
+pub fn solve_part2(input: &str) -> usize {
+    let (rules, updates) = parse(input);
+    updates
+        .into_iter()
+        .filter(|update| !in_order(&rules, update))
+        .map(|mut remaining| {
+            let mut ordered = Vec::with_capacity(remaining.len());
+            while !remaining.is_empty() {
+                let next = (0..remaining.len())
+                    .find(|&i| {
+                        remaining
+                            .iter()
+                            .all(|&other| !rules.contains(&(other, remaining[i])))
+                    })
+                    .expect("rules are not contradictory");
+                ordered.push(remaining.remove(next));
+            }
+            ordered[ordered.len() / 2] as usize
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_traits::AdventOfCodeDay;
+    use proptest::prelude::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::{gen, Solver};
+
+    proptest! {
+        #[test]
+        fn test_against_reference(seed: u64, size in 1usize..50) {
+            let input = gen::generate(&mut StdRng::seed_from_u64(seed), size);
+            let parsed = Solver::parse_input(&input);
+            prop_assert_eq!(Solver::solve_part1(&parsed), super::solve_part1(&input));
+            prop_assert_eq!(Solver::solve_part2(&parsed), super::solve_part2(&input));
+        }
+    }
+}
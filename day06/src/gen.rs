@@ -0,0 +1,43 @@
+//! Random inputs in the shape of the puzzle input.
+
+use grid::{Direction, Grid, Pos};
+use rand::Rng;
+
+/// Side length of the real puzzle input.
+pub const DEFAULT_SIZE: usize = 130;
+
+/// Generates a `size` by `size` lab with scattered obstructions and the guard
+/// facing up.
+///
+/// Like in the puzzle the guard always leaves the lab eventually, maps where
+/// she would walk in circles are thrown away.
+pub fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+    let size = size.max(1);
+    loop {
+        let cells = (0..size * size)
+            .map(|_| if rng.gen_bool(0.08) { b'#' } else { b'.' })
+            .collect();
+        let mut grid = Grid::from_vec(size, size, cells).unwrap();
+        let guard = (rng.gen_range(0..size), rng.gen_range(0..size));
+        grid[guard] = b'^';
+        if leaves(&grid, guard) {
+            return grid.to_string();
+        }
+    }
+}
+
+fn leaves(grid: &Grid, mut pos: Pos) -> bool {
+    let mut dir = Direction::Up;
+    let mut seen = Grid::new(grid.width(), grid.height(), 0u8);
+    loop {
+        if seen[pos] & 1 << dir as u8 != 0 {
+            return false;
+        }
+        seen[pos] |= 1 << dir as u8;
+        match grid.step(pos, dir) {
+            None => return true,
+            Some(next) if grid[next] == b'#' => dir = dir.turn_right(),
+            Some(next) => pos = next,
+        }
+    }
+}
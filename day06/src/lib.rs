@@ -1,122 +1,49 @@
+pub mod gen;
+pub mod vis;
+
 use rustc_hash::FxHashSet as HashSet;
 use std::str::FromStr;
 
 use aoc_traits::AdventOfCodeDay;
+use grid::{Direction, Grid, Pos};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 
-#[derive(Debug, Clone)]
-struct Storage {
-    storage: Vec<u8>,
-    x: usize,
-}
+/// The directions in which the guard has left each tile, one bit per direction.
+type Visited = Grid<u8>;
 
-impl Storage {
-    fn new(x: usize, y: usize) -> Self {
-        Self {
-            storage: vec![0; x * y],
-            x,
-        }
-    }
-    fn contains(&self, x: usize, y: usize, dir: Direction) -> bool {
-        self.storage[y * self.x + x] & dir as u8 != 0
-    }
-    fn contains_any(&self, x: usize, y: usize) -> bool {
-        self.storage[y * self.x + x] != 0
-    }
-    fn insert(&mut self, x: usize, y: usize, dir: Direction) {
-        self.storage[y * self.x + x] |= dir as u8;
-    }
+fn bit(dir: Direction) -> u8 {
+    1 << dir as u8
 }
 
 #[derive(Debug, Clone)]
-pub struct Grid {
-    x: usize,
-    y: usize,
-    stones: Storage,
-    player: (usize, usize),
+pub struct Lab {
+    grid: Grid,
+    player: Pos,
 }
 
-impl FromStr for Grid {
+impl FromStr for Lab {
     type Err = eyre::Report;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut player = None;
-
-        let x = s
-            .lines()
-            .next()
-            .ok_or_else(|| eyre::eyre!("empty input"))?
-            .len();
-        let y = s.lines().count();
-        let mut stones = Storage::new(x, y);
-
-        for (y, line) in s.lines().enumerate() {
-            for (x, c) in line.chars().enumerate() {
-                if c == '^' {
-                    player = Some((x, y));
-                } else if c == '#' {
-                    stones.insert(x, y, Direction::Up);
-                }
-            }
-        }
-        Ok(Grid {
-            x,
-            y,
-            player: player.expect("have a player"),
-            stones,
-        })
-    }
-}
-
-impl Grid {
-    fn in_bounds(&self, x: isize, y: isize) -> bool {
-        x >= 0 && y >= 0 && x < self.x as isize && y < self.y as isize
+        let grid: Grid = s.parse()?;
+        let player = grid.marker(b'^')?;
+        Ok(Lab { grid, player })
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-#[repr(u8)]
-enum Direction {
-    Up = 1,
-    Down = 2,
-    Left = 4,
-    Right = 8,
-}
-
-impl Direction {
-    fn turn(self) -> Self {
-        match self {
-            Self::Up => Self::Right,
-            Self::Left => Self::Up,
-            Self::Down => Self::Left,
-            Self::Right => Self::Down,
-        }
-    }
-    fn step(&self, player: (isize, isize)) -> (isize, isize) {
-        match self {
-            Self::Up => (player.0, player.1 - 1),
-            Self::Left => (player.0 - 1, player.1),
-            Self::Down => (player.0, player.1 + 1),
-            Self::Right => (player.0 + 1, player.1),
-        }
-    }
-}
-
-fn part1(grid: &Grid) -> usize {
+fn part1(lab: &Lab) -> usize {
     let mut visited = HashSet::default();
     let mut dir = Direction::Up;
-    let mut player = (grid.player.0 as isize, grid.player.1 as isize);
+    let mut player = lab.player;
     loop {
         visited.insert(player);
 
-        let next_player = dir.step(player);
-        if !grid.in_bounds(next_player.0, next_player.1) {
+        let Some(next_player) = lab.grid.step(player, dir) else {
             break;
-        }
-        if grid
-            .stones
-            .contains_any(next_player.0 as usize, next_player.1 as usize)
-        {
-            dir = dir.turn();
+        };
+        if lab.grid[next_player] == b'#' {
+            dir = dir.turn_right();
         } else {
             player = next_player;
         }
@@ -124,82 +51,120 @@ fn part1(grid: &Grid) -> usize {
     visited.len()
 }
 
-fn check_if_loops(
-    grid: &Grid,
-    mut visited: Storage,
-    mut player: (isize, isize),
-    mut dir: Direction,
-    new_rock: (isize, isize),
-) -> bool {
-    if !grid.in_bounds(new_rock.0, new_rock.1) {
-        return false;
-    }
-    // if we put a rock in this position we could not have gotten here at all
-    if visited.contains_any(new_rock.0 as usize, new_rock.1 as usize) {
-        return false;
-    }
-    loop {
-        if visited.contains(player.0 as usize, player.1 as usize, dir) {
-            return true;
-        }
-        visited.insert(player.0 as usize, player.1 as usize, dir);
+/// A spot where a new obstruction could go, right in front of the guard on her
+/// original route.
+#[derive(Debug, Clone, Copy)]
+struct Candidate {
+    /// Number of steps the guard took before reaching `player`.
+    step: usize,
+    player: Pos,
+    dir: Direction,
+    new_rock: Pos,
+}
 
-        let next_player = dir.step(player);
-        if next_player == new_rock {
-            dir = dir.turn();
-            continue;
-        }
-        if !grid.in_bounds(next_player.0, next_player.1) {
-            break;
-        }
-        if grid
-            .stones
-            .contains_any(next_player.0 as usize, next_player.1 as usize)
-        {
-            dir = dir.turn();
-        } else {
-            player = next_player;
-        }
-    }
-    false
+/// The original route of the guard.
+struct Route {
+    /// The step at which the guard first left each tile in each direction,
+    /// `usize::MAX` if she never did.
+    first_left: Grid<[usize; 4]>,
+    /// The tiles in front of the guard that she has not been on yet, in the
+    /// order she reaches them.
+    candidates: Vec<Candidate>,
 }
 
-fn part2(grid: &Grid) -> usize {
-    let mut potential_loops = HashSet::default();
-    let mut visited = Storage::new(grid.x, grid.y);
-    let mut dir = Direction::Up;
-    let mut player = (grid.player.0 as isize, grid.player.1 as isize);
-    loop {
-        if check_if_loops(grid, visited.clone(), player, dir.turn(), dir.step(player)) {
-            potential_loops.insert(dir.step(player));
+impl Route {
+    fn walk(lab: &Lab) -> Self {
+        let mut first_left = Grid::new(lab.grid.width(), lab.grid.height(), [usize::MAX; 4]);
+        let mut candidates = Vec::new();
+        let mut dir = Direction::Up;
+        let mut player = lab.player;
+        let mut step = 0;
+        while let Some(next_player) = lab.grid.step(player, dir) {
+            // if we put a rock in this position we could not have gotten here at all
+            if first_left[next_player] == [usize::MAX; 4] {
+                candidates.push(Candidate {
+                    step,
+                    player,
+                    dir,
+                    new_rock: next_player,
+                });
+            }
+            let first = &mut first_left[player][dir as usize];
+            *first = (*first).min(step);
+            step += 1;
+
+            if lab.grid[next_player] == b'#' {
+                dir = dir.turn_right();
+            } else {
+                player = next_player;
+            }
         }
-        visited.insert(player.0 as usize, player.1 as usize, dir);
-
-        let next_player = dir.step(player);
-        if !grid.in_bounds(next_player.0, next_player.1) {
-            break;
+        Route {
+            first_left,
+            candidates,
         }
-        if grid
-            .stones
-            .contains_any(next_player.0 as usize, next_player.1 as usize)
-        {
-            dir = dir.turn();
-        } else {
-            player = next_player;
+    }
+
+    /// Whether the guard walks in circles after turning at `candidate`.
+    ///
+    /// Reaching a state from before the candidate also counts, the guard
+    /// would then follow her original route back to the candidate.
+    fn loops(&self, lab: &Lab, candidate: &Candidate) -> bool {
+        let Candidate {
+            step,
+            mut player,
+            dir,
+            new_rock,
+        } = *candidate;
+        let mut dir = dir.turn_right();
+        let mut visited = Visited::new(lab.grid.width(), lab.grid.height(), 0);
+        loop {
+            if visited[player] & bit(dir) != 0 || self.first_left[player][dir as usize] < step {
+                return true;
+            }
+            visited[player] |= bit(dir);
+
+            let Some(next_player) = lab.grid.step(player, dir) else {
+                break;
+            };
+            if next_player == new_rock || lab.grid[next_player] == b'#' {
+                dir = dir.turn_right();
+            } else {
+                player = next_player;
+            }
         }
+        false
     }
+}
+
+fn part2(lab: &Lab) -> usize {
+    let route = Route::walk(lab);
+    #[cfg(feature = "parallel")]
+    let candidates = route.candidates.par_iter();
+    #[cfg(not(feature = "parallel"))]
+    let candidates = route.candidates.iter();
+    let potential_loops: HashSet<Pos> = candidates
+        .filter(|candidate| route.loops(lab, candidate))
+        .map(|candidate| candidate.new_rock)
+        .collect();
     potential_loops.len()
 }
 
 #[derive(Default)]
 pub struct Solver;
+impl Solver {
+    pub fn try_parse_input(input: &str) -> Result<Lab, eyre::Report> {
+        input.parse()
+    }
+}
+
 impl AdventOfCodeDay for Solver {
-    type ParsedInput<'a> = Grid;
+    type ParsedInput<'a> = Lab;
     type Part1Output = usize;
     type Part2Output = usize;
 
     fn parse_input(input: &str) -> Self::ParsedInput<'_> {
-        input.parse().expect("Failed to parse input")
+        Self::try_parse_input(input).expect("Failed to parse input")
     }
 
     fn solve_part1(input: &Self::ParsedInput<'_>) -> Self::Part1Output {
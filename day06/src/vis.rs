@@ -0,0 +1,57 @@
+//! Frames of the guard walking through the lab.
+
+use grid::{Direction, Grid};
+
+use crate::{Lab, Route};
+
+/// The guard walking until she leaves the lab, one frame per step, with an `X`
+/// on every tile she has been on.
+pub fn part1(lab: &Lab, frame: &mut dyn FnMut(&Grid)) {
+    let mut grid = lab.grid.clone();
+    let mut dir = Direction::Up;
+    let mut player = lab.player;
+    loop {
+        grid[player] = dir.arrow();
+        frame(&grid);
+        grid[player] = b'X';
+        let Some(next_player) = lab.grid.step(player, dir) else {
+            break;
+        };
+        if lab.grid[next_player] == b'#' {
+            dir = dir.turn_right();
+        } else {
+            player = next_player;
+        }
+    }
+    frame(&grid);
+}
+
+/// Like [`part1`], but every new obstruction that would trap the guard in a
+/// loop is drawn as an `O` as soon as it is found.
+pub fn part2(lab: &Lab, frame: &mut dyn FnMut(&Grid)) {
+    let route = Route::walk(lab);
+    let mut candidates = route.candidates.iter().peekable();
+    let mut grid = lab.grid.clone();
+    let mut dir = Direction::Up;
+    let mut player = lab.player;
+    let mut step = 0;
+    while let Some(next_player) = lab.grid.step(player, dir) {
+        if let Some(candidate) = candidates.next_if(|candidate| candidate.step == step) {
+            if route.loops(lab, candidate) {
+                grid[next_player] = b'O';
+            }
+        }
+        step += 1;
+        let tile = if grid[player] == b'O' { b'O' } else { b'X' };
+        grid[player] = dir.arrow();
+        frame(&grid);
+        grid[player] = tile;
+
+        if lab.grid[next_player] == b'#' {
+            dir = dir.turn_right();
+        } else {
+            player = next_player;
+        }
+    }
+    frame(&grid);
+}
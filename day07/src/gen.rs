@@ -0,0 +1,42 @@
+//! Random inputs in the shape of the puzzle input.
+
+use std::fmt::Write;
+
+use itertools::Itertools;
+use rand::Rng;
+
+/// Number of equations in the real puzzle input.
+pub const DEFAULT_SIZE: usize = 850;
+
+/// Generates `size` calibration equations.
+///
+/// Targets are computed from random operators, so most equations can be
+/// solved, a few get a random target instead.
+pub fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+    let mut out = String::new();
+    for _ in 0..size {
+        let (target, numbers) = loop {
+            let numbers: Vec<u64> = (0..rng.gen_range(2..=12))
+                .map(|_| rng.gen_range(1..1000))
+                .collect();
+            let target =
+                numbers[1..]
+                    .iter()
+                    .try_fold(numbers[0], |acc, &n| match rng.gen_range(0..3) {
+                        0 => acc.checked_add(n),
+                        1 => acc.checked_mul(n),
+                        _ => acc.checked_mul(10u64.pow(n.ilog10() + 1))?.checked_add(n),
+                    });
+            // keep the targets in the range of the real input
+            if let Some(target) = target.filter(|&t| t < 1 << 48) {
+                break (target, numbers);
+            }
+        };
+        let target = match rng.gen_bool(0.1) {
+            true => rng.gen_range(1..=target),
+            false => target,
+        };
+        writeln!(out, "{target}: {}", numbers.iter().join(" ")).unwrap();
+    }
+    out
+}
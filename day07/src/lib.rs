@@ -1,6 +1,12 @@
+pub mod gen;
+
 use std::str::FromStr;
 
 use aoc_traits::AdventOfCodeDay;
+use eyre::ensure;
+use parsing::{error_at, parse_at};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 
 #[derive(Debug, Clone)]
 pub struct Equation {
@@ -12,16 +18,23 @@ impl FromStr for Equation {
     type Err = eyre::Report;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (target, numbers) = s.split_once(": ").ok_or_else(|| eyre::eyre!("no colon"))?;
-        let target = target.parse()?;
+        Equation::parse(s, s)
+    }
+}
+impl Equation {
+    /// Parses `line`, reporting errors relative to the whole `input`.
+    fn parse(input: &str, line: &str) -> Result<Self, eyre::Report> {
+        let (target, numbers) = line
+            .split_once(": ")
+            .ok_or_else(|| error_at(input, line, "no colon"))?;
+        let target = parse_at(input, target)?;
         let numbers = numbers
             .split_whitespace()
-            .map(|n| n.parse())
+            .map(|n| parse_at(input, n))
             .collect::<Result<Vec<_>, _>>()?;
+        ensure!(!numbers.is_empty(), error_at(input, line, "no numbers"));
         Ok(Equation { target, numbers })
     }
-}
-impl Equation {
     fn solvable_part1(&self) -> bool {
         check_equation(self.target, &self.numbers)
     }
@@ -68,13 +81,24 @@ pub struct Game {
     equations: Vec<Equation>,
 }
 
+impl Game {
+    /// Sum of the targets of all equations that `solvable` accepts.
+    fn calibration(&self, solvable: impl Fn(&Equation) -> bool + Sync) -> u64 {
+        #[cfg(feature = "parallel")]
+        let equations = self.equations.par_iter();
+        #[cfg(not(feature = "parallel"))]
+        let equations = self.equations.iter();
+        equations.filter(|e| solvable(e)).map(|e| e.target).sum()
+    }
+}
+
 impl FromStr for Game {
     type Err = eyre::Report;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let equations = s
             .lines()
-            .map(|l| l.parse())
+            .map(|l| Equation::parse(s, l))
             .collect::<Result<Vec<_>, _>>()?;
         Ok(Game { equations })
     }
@@ -82,37 +106,26 @@ impl FromStr for Game {
 
 #[derive(Default)]
 pub struct Solver;
+impl Solver {
+    pub fn try_parse_input(input: &str) -> Result<Game, eyre::Report> {
+        input.parse()
+    }
+}
+
 impl AdventOfCodeDay for Solver {
     type ParsedInput<'a> = Game;
     type Part1Output = u64;
     type Part2Output = u64;
 
     fn parse_input(input: &str) -> Self::ParsedInput<'_> {
-        input.parse().expect("Failed to parse input")
+        Self::try_parse_input(input).expect("Failed to parse input")
     }
 
     fn solve_part1(input: &Self::ParsedInput<'_>) -> Self::Part1Output {
-        input
-            .equations
-            .iter()
-            .filter(|e| e.solvable_part1())
-            .map(|e| e.target)
-            .sum()
+        input.calibration(Equation::solvable_part1)
     }
     fn solve_part2(input: &Self::ParsedInput<'_>) -> Self::Part2Output {
-        input
-            .equations
-            .iter()
-            .map(|e| {
-                if e.solvable_part1() {
-                    e.target
-                } else if e.solvable_part2() {
-                    e.target
-                } else {
-                    0
-                }
-            })
-            .sum()
+        input.calibration(|e| e.solvable_part1() || e.solvable_part2())
     }
 }
 
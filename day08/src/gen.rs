@@ -0,0 +1,22 @@
+//! Random inputs in the shape of the puzzle input.
+
+use grid::Grid;
+use rand::{seq::SliceRandom, Rng};
+
+/// Side length of the real puzzle input.
+pub const DEFAULT_SIZE: usize = 50;
+
+const FREQUENCIES: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// Generates a `size` by `size` map with a handful of antennas per frequency.
+pub fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+    let size = size.max(1);
+    let mut grid = Grid::new(size, size, b'.');
+    let frequencies = (size * size / 60).clamp(1, FREQUENCIES.len());
+    for &frequency in FREQUENCIES.choose_multiple(rng, frequencies) {
+        for _ in 0..rng.gen_range(2..=4) {
+            grid[(rng.gen_range(0..size), rng.gen_range(0..size))] = frequency;
+        }
+    }
+    grid.to_string()
+}
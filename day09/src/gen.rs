@@ -0,0 +1,19 @@
+//! Random inputs in the shape of the puzzle input.
+
+use rand::Rng;
+
+/// Number of files in the real puzzle input.
+pub const DEFAULT_SIZE: usize = 10000;
+
+/// Generates a disk map of `size` files with free space between them.
+pub fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+    let mut out = String::with_capacity(2 * size);
+    for file in 0..size {
+        if file > 0 {
+            out.push(char::from(b'0' + rng.gen_range(0..10)));
+        }
+        out.push(char::from(b'0' + rng.gen_range(1..10)));
+    }
+    out.push('\n');
+    out
+}
@@ -1,6 +1,9 @@
+pub mod gen;
+
 use std::{collections::VecDeque, str::FromStr};
 
 use aoc_traits::AdventOfCodeDay;
+use parsing::error_at;
 
 #[derive(Debug, Clone)]
 pub struct HardDrive {
@@ -19,6 +22,10 @@ impl FromStr for HardDrive {
     type Err = eyre::Report;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim_end();
+        if let Some(idx) = s.find(|c: char| !c.is_ascii_digit()) {
+            return Err(error_at(s, &s[idx..], "expected a digit"));
+        }
         let mut start = 0;
         let mut idx = 0;
         let mut res = Vec::with_capacity(s.len() / 2 + 1);
@@ -135,13 +142,19 @@ impl HardDrive {
 
 #[derive(Default)]
 pub struct Solver;
+impl Solver {
+    pub fn try_parse_input(input: &str) -> Result<HardDrive, eyre::Report> {
+        input.parse()
+    }
+}
+
 impl AdventOfCodeDay for Solver {
     type ParsedInput<'a> = HardDrive;
     type Part1Output = usize;
     type Part2Output = usize;
 
     fn parse_input(input: &str) -> Self::ParsedInput<'_> {
-        input.parse().expect("Failed to parse input")
+        Self::try_parse_input(input).expect("Failed to parse input")
     }
 
     fn solve_part1(input: &Self::ParsedInput<'_>) -> Self::Part1Output {
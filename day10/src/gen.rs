@@ -0,0 +1,28 @@
+//! Random inputs in the shape of the puzzle input.
+
+use grid::Grid;
+use rand::Rng;
+
+/// Side length of the real puzzle input.
+pub const DEFAULT_SIZE: usize = 50;
+
+/// Generates a `size` by `size` topographic map.
+///
+/// Random heights alone hardly ever form a trail, so hiking trails that climb
+/// from 0 to 9 are drawn on top of them.
+pub fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+    let size = size.max(1);
+    let cells = (0..size * size)
+        .map(|_| b'0' + rng.gen_range(0..10))
+        .collect();
+    let mut grid = Grid::from_vec(size, size, cells).unwrap();
+    for _ in 0..size * size / 25 {
+        let mut pos = (rng.gen_range(0..size), rng.gen_range(0..size));
+        for height in b'0'..=b'9' {
+            grid[pos] = height;
+            let neighbours: Vec<_> = grid.neighbours4(pos).collect();
+            pos = neighbours[rng.gen_range(0..neighbours.len())];
+        }
+    }
+    grid.to_string()
+}
@@ -1,78 +1,79 @@
+pub mod gen;
+
 use std::{iter, str::FromStr};
 
 use aoc_traits::AdventOfCodeDay;
+use eyre::ensure;
+use grid::{Grid, Pos};
+use parsing::Location;
 use rustc_hash::{FxHashMap, FxHashSet};
 
 #[derive(Debug, Clone)]
-pub struct Grid {
-    x: usize,
-    y: usize,
-    classes: Vec<Vec<(isize, isize)>>,
+pub struct TopoMap {
+    grid: Grid,
+    classes: Vec<Vec<Pos>>,
 }
 
-impl FromStr for Grid {
+impl FromStr for TopoMap {
     type Err = eyre::Report;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut g_y = 0;
-        let mut g_x = 0;
-        let mut classes: Vec<Vec<(isize, isize)>> = vec![Vec::with_capacity(200); 10];
-        for (y, line) in s.lines().enumerate() {
-            g_y = y;
-            g_x = line.len();
-            for (x, c) in line.chars().enumerate() {
-                if c != '.' {
-                    let class = c as usize - '0' as usize;
-                    classes[class].push((x as isize, y as isize));
-                }
+        let grid: Grid = s.parse()?;
+        let mut classes: Vec<Vec<Pos>> = vec![Vec::with_capacity(200); 10];
+        for ((x, y), &c) in grid.iter() {
+            if c != b'.' {
+                let location = Location {
+                    line: y + 1,
+                    column: x + 1,
+                };
+                ensure!(
+                    c.is_ascii_digit(),
+                    "{location}: invalid height {:?}",
+                    c as char
+                );
+                classes[(c - b'0') as usize].push((x, y));
             }
         }
-        Ok(Grid {
-            x: g_x,
-            y: g_y + 1,
-            classes,
-        })
+        Ok(TopoMap { grid, classes })
     }
 }
 
-impl Grid {
-    fn in_bounds(&self, x: isize, y: isize) -> bool {
-        x >= 0 && y >= 0 && x < self.x as isize && y < self.y as isize
+#[derive(Default)]
+pub struct Solver;
+impl Solver {
+    pub fn try_parse_input(input: &str) -> Result<TopoMap, eyre::Report> {
+        input.parse()
     }
 }
 
-#[derive(Default)]
-pub struct Solver;
 impl AdventOfCodeDay for Solver {
-    type ParsedInput<'a> = Grid;
+    type ParsedInput<'a> = TopoMap;
     type Part1Output = usize;
     type Part2Output = usize;
 
     fn parse_input(input: &str) -> Self::ParsedInput<'_> {
-        input.parse().expect("Failed to parse input")
+        Self::try_parse_input(input).expect("Failed to parse input")
     }
 
     fn solve_part1(input: &Self::ParsedInput<'_>) -> Self::Part1Output {
         let mut current_level: FxHashMap<_, _> = input.classes[9]
             .iter()
-            .map(|&(x, y)| ((x, y), FxHashSet::from_iter(iter::once((x, y)))))
+            .map(|&pos| (pos, FxHashSet::from_iter(iter::once(pos))))
             .collect();
         for level in (0..9).rev() {
             let next_level = input.classes[level]
                 .iter()
-                .flat_map(|&(x, y)| {
+                .flat_map(|&pos| {
                     let mut reachable_peaks = FxHashSet::default();
-                    for (x, y) in [(x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)] {
-                        if input.in_bounds(x, y) {
-                            if let Some(peaks) = current_level.get(&(x, y)) {
-                                reachable_peaks.extend(peaks.iter());
-                            }
+                    for neighbour in input.grid.neighbours4(pos) {
+                        if let Some(peaks) = current_level.get(&neighbour) {
+                            reachable_peaks.extend(peaks.iter());
                         }
                     }
                     if reachable_peaks.is_empty() {
                         None
                     } else {
-                        Some(((x, y), reachable_peaks))
+                        Some((pos, reachable_peaks))
                     }
                 })
                 .collect();
@@ -82,23 +83,21 @@ impl AdventOfCodeDay for Solver {
     }
     fn solve_part2(input: &Self::ParsedInput<'_>) -> Self::Part2Output {
         let mut current_level: FxHashMap<_, _> =
-            input.classes[9].iter().map(|&(x, y)| ((x, y), 1)).collect();
+            input.classes[9].iter().map(|&pos| (pos, 1)).collect();
         for level in (0..9).rev() {
             let next_level = input.classes[level]
                 .iter()
-                .flat_map(|&(x, y)| {
+                .flat_map(|&pos| {
                     let mut count = 0;
-                    for (x, y) in [(x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)] {
-                        if input.in_bounds(x, y) {
-                            if let Some(peaks) = current_level.get(&(x, y)) {
-                                count += peaks;
-                            }
+                    for neighbour in input.grid.neighbours4(pos) {
+                        if let Some(peaks) = current_level.get(&neighbour) {
+                            count += peaks;
                         }
                     }
                     if count == 0 {
                         None
                     } else {
-                        Some(((x, y), count))
+                        Some((pos, count))
                     }
                 })
                 .collect();
@@ -0,0 +1,16 @@
+//! Random inputs in the shape of the puzzle input.
+
+use itertools::Itertools;
+use rand::Rng;
+
+/// Number of stones in the real puzzle input.
+pub const DEFAULT_SIZE: usize = 8;
+
+/// Generates a line of `size` stones with up to seven digits.
+pub fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+    let stones = (0..size).map(|_| match rng.gen_range(0..4) {
+        0 => rng.gen_range(0..10),
+        _ => rng.gen_range(0..10_000_000u64),
+    });
+    format!("{}\n", stones.format(" "))
+}
@@ -1,3 +1,5 @@
+pub mod gen;
+
 use rustc_hash::FxHashMap;
 #[memoize::memoize(CustomHasher: FxHashMap, HasherInit: FxHashMap::default())]
 fn step(number: u64, depth: usize) -> usize {
@@ -14,34 +16,79 @@ fn step(number: u64, depth: usize) -> usize {
     }
 }
 
+fn blink(input: &str, blinks: usize) -> usize {
+    let res = input
+        .split_ascii_whitespace()
+        .map(|x| x.parse::<u64>().unwrap())
+        .map(|x| step(x, blinks))
+        .sum();
+    memoized_flush_step();
+    res
+}
+
+/// Puzzle parameters that differ between the parts.
+#[derive(Debug, Clone)]
+pub struct Params {
+    /// Number of blinks in part 1.
+    pub blinks1: usize,
+    /// Number of blinks in part 2.
+    pub blinks2: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            blinks1: 25,
+            blinks2: 75,
+        }
+    }
+}
+
+impl Params {
+    /// Overrides a single parameter by name.
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), eyre::Report> {
+        match key {
+            "blinks1" => self.blinks1 = value.parse()?,
+            "blinks2" => self.blinks2 = value.parse()?,
+            _ => eyre::bail!("unknown parameter {key}, expected blinks1 or blinks2"),
+        }
+        Ok(())
+    }
+}
+
 use aoc_traits::AdventOfCodeDay;
+use parsing::parse_at;
 pub struct Solver;
+impl Solver {
+    /// Checks that every stone is a number, the solvers work on the text itself.
+    pub fn try_parse_input(input: &str) -> Result<&str, eyre::Report> {
+        for stone in input.split_ascii_whitespace() {
+            parse_at::<u64>(input, stone)?;
+        }
+        Ok(input)
+    }
+    pub fn solve_part1_with(input: &str, params: &Params) -> usize {
+        blink(input, params.blinks1)
+    }
+    pub fn solve_part2_with(input: &str, params: &Params) -> usize {
+        blink(input, params.blinks2)
+    }
+}
+
 impl AdventOfCodeDay for Solver {
     type ParsedInput<'a> = &'a str;
     type Part1Output = usize;
     type Part2Output = usize;
 
     fn parse_input(input: &str) -> Self::ParsedInput<'_> {
-        input
+        Self::try_parse_input(input).expect("Failed to parse input")
     }
 
     fn solve_part1(input: &Self::ParsedInput<'_>) -> Self::Part1Output {
-        let res = input
-            .split_ascii_whitespace()
-            .map(|x| x.parse::<u64>().unwrap())
-            .map(|x| step(x, 25))
-            .sum();
-        memoized_flush_step();
-        res
+        Self::solve_part1_with(input, &Params::default())
     }
     fn solve_part2(input: &Self::ParsedInput<'_>) -> Self::Part2Output {
-        let res = input
-            .split_ascii_whitespace()
-            .map(|x| x.parse::<u64>().unwrap())
-            .map(|x| step(x, 75))
-            .sum();
-        memoized_flush_step();
-        res
+        Self::solve_part2_with(input, &Params::default())
     }
 }
 
@@ -49,9 +96,19 @@ impl AdventOfCodeDay for Solver {
 mod tests {
     use aoc_traits::AdventOfCodeDay;
 
-    use crate::Solver;
+    use crate::{Params, Solver};
     const TEST_INPUT: &str = "125 17";
 
+    #[test]
+    fn test_params() {
+        let parsed = Solver::parse_input(TEST_INPUT);
+        let params = Params {
+            blinks1: 6,
+            ..Default::default()
+        };
+        assert_eq!(Solver::solve_part1_with(parsed, &params), 22);
+    }
+
     #[test]
     fn test_stage1() {
         let parsed = Solver::parse_input(TEST_INPUT);
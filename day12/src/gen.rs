@@ -0,0 +1,33 @@
+//! Random inputs in the shape of the puzzle input.
+
+use rand::Rng;
+
+/// Side length of the real puzzle input.
+pub const DEFAULT_SIZE: usize = 140;
+
+/// Generates a `size` by `size` garden of plant regions.
+///
+/// Most plots copy a neighbour, so plants form connected regions of varying
+/// shape, and the same plant shows up in several separate regions.
+pub fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+    let size = size.max(1);
+    let mut rows: Vec<Vec<u8>> = Vec::with_capacity(size);
+    for y in 0..size {
+        let mut row = Vec::with_capacity(size);
+        for x in 0..size {
+            let plant = match rng.gen_range(0..20) {
+                0..=8 if x > 0 => row[x - 1],
+                9..=17 if y > 0 => rows[y - 1][x],
+                _ => b'A' + rng.gen_range(0..26),
+            };
+            row.push(plant);
+        }
+        rows.push(row);
+    }
+    let mut out = String::with_capacity(size * (size + 1));
+    for row in rows {
+        out.push_str(std::str::from_utf8(&row).unwrap());
+        out.push('\n');
+    }
+    out
+}
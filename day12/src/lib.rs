@@ -1,6 +1,9 @@
+pub mod gen;
+
 use std::str::FromStr;
 
 use aoc_traits::AdventOfCodeDay;
+use parsing::error_at;
 
 #[derive(Debug, Clone)]
 pub struct Grid {
@@ -13,7 +16,10 @@ impl FromStr for Grid {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut classes: Vec<Vec<(isize, isize)>> = vec![Vec::with_capacity(200); 256];
         for (y, line) in s.lines().enumerate() {
-            for (x, c) in line.chars().enumerate() {
+            for (x, (idx, c)) in line.char_indices().enumerate() {
+                if !c.is_ascii() {
+                    return Err(error_at(s, &line[idx..], format!("invalid plant {c:?}")));
+                }
                 if c != '.' {
                     let class = c as usize;
                     classes[class].push((x as isize, y as isize));
@@ -126,13 +132,19 @@ fn fence_cost_two(area: &[(isize, isize)]) -> usize {
 
 #[derive(Default)]
 pub struct Solver;
+impl Solver {
+    pub fn try_parse_input(input: &str) -> Result<Grid, eyre::Report> {
+        input.parse()
+    }
+}
+
 impl AdventOfCodeDay for Solver {
     type ParsedInput<'a> = Grid;
     type Part1Output = usize;
     type Part2Output = usize;
 
     fn parse_input(input: &str) -> Self::ParsedInput<'_> {
-        input.parse().expect("Failed to parse input")
+        Self::try_parse_input(input).expect("Failed to parse input")
     }
 
     fn solve_part1(input: &Self::ParsedInput<'_>) -> Self::Part1Output {
@@ -0,0 +1,39 @@
+//! Random inputs in the shape of the puzzle input.
+
+use std::fmt::Write;
+
+use rand::Rng;
+
+/// Number of claw machines in the real puzzle input.
+pub const DEFAULT_SIZE: usize = 320;
+
+/// Generates `size` claw machines.
+///
+/// About a third of the prizes are reachable with a whole number of presses.
+pub fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+    let mut out = String::new();
+    for idx in 0..size {
+        let a = (rng.gen_range(10..100), rng.gen_range(10..100));
+        let b = loop {
+            let b = (rng.gen_range(10..100), rng.gen_range(10..100));
+            // like in the real input the buttons are never parallel
+            if a.0 * b.1 != a.1 * b.0 {
+                break b;
+            }
+        };
+        let prize = match rng.gen_range(0..3) {
+            0 => {
+                let (na, nb) = (rng.gen_range(0..100), rng.gen_range(0..100));
+                (a.0 * na + b.0 * nb, a.1 * na + b.1 * nb)
+            }
+            _ => (rng.gen_range(1000..20000), rng.gen_range(1000..20000)),
+        };
+        if idx > 0 {
+            out.push('\n');
+        }
+        writeln!(out, "Button A: X+{}, Y+{}", a.0, a.1).unwrap();
+        writeln!(out, "Button B: X+{}, Y+{}", b.0, b.1).unwrap();
+        writeln!(out, "Prize: X={}, Y={}", prize.0, prize.1).unwrap();
+    }
+    out
+}
@@ -1,3 +1,7 @@
+pub mod gen;
+#[cfg(feature = "reference")]
+pub mod reference;
+
 #[derive(Debug)]
 pub struct Game {
     a_vec: (i64, i64),
@@ -48,55 +52,122 @@ fn parse_games(input: &str) -> IResult<&str, Vec<Game>> {
 }
 
 impl Game {
-    fn solve(&self) -> Option<i64> {
-        let v_num = self.a_vec.1 * self.target.0 - self.a_vec.0 * self.target.1;
-        let v_den = self.a_vec.1 * self.b_vec.0 - self.a_vec.0 * self.b_vec.1;
-        if v_num % v_den != 0 {
+    /// The cheapest presses of A and B, at most `limit` of each, that reach the
+    /// prize, if any.
+    fn presses(&self, limit: i64) -> Option<(i64, i64)> {
+        let ((ax, ay), (bx, by), (tx, ty)) = (self.a_vec, self.b_vec, self.target);
+        let det = ax * by - ay * bx;
+        if det == 0 {
+            return self.parallel_presses(limit);
+        }
+        // Cramer's rule, the solution is unique
+        let (u, v) = (tx * by - ty * bx, ax * ty - ay * tx);
+        if u % det != 0 || v % det != 0 {
             return None;
         }
-        let v = v_num / v_den;
-        let u_num = self.target.0 - self.b_vec.0 * v;
-        let u_den = self.a_vec.0;
-        if u_num % u_den != 0 {
+        let (u, v) = (u / det, v / det);
+        let presses = 0..=limit;
+        (presses.contains(&u) && presses.contains(&v)).then_some((u, v))
+    }
+
+    /// [`Game::presses`] for buttons that move in the same direction, where
+    /// there can be many ways to reach the prize.
+    ///
+    /// The presses are solved along one axis the buttons move on, and the
+    /// cheapest solution is checked on both.
+    fn parallel_presses(&self, limit: i64) -> Option<(i64, i64)> {
+        let ((ax, ay), (bx, by), (tx, ty)) = (self.a_vec, self.b_vec, self.target);
+        let (p, q, t) = match ax != 0 || bx != 0 {
+            true => (ax, bx, tx),
+            false => (ay, by, ty),
+        };
+        let (g, x, y) = ext_gcd(p, q);
+        if g == 0 {
+            // neither button moves
+            return (self.target == (0, 0)).then_some((0, 0));
+        }
+        if t % g != 0 {
             return None;
         }
-        let u = u_num / u_den;
-        Some(u * 3 + v)
+        // all solutions of `u * p + v * q = t` are `(u0 + k * s, v0 - k * r)`
+        let (u0, v0) = (x * (t / g), y * (t / g));
+        let (s, r) = (q / g, p / g);
+        let (low_u, high_u) = steps(u0, s, limit)?;
+        let (low_v, high_v) = steps(v0, -r, limit)?;
+        let (low, high) = (low_u.max(low_v), high_u.min(high_v));
+        if low > high {
+            return None;
+        }
+        // a step changes the cost by `3 * s - r`
+        let k = if 3 * s > r { low } else { high };
+        let (u, v) = (u0 + k * s, v0 - k * r);
+        (u * ax + v * bx == tx && u * ay + v * by == ty).then_some((u, v))
     }
 }
 
+/// `(gcd(a, b), x, y)` with `a * x + b * y = gcd(a, b)`.
+fn ext_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    if b == 0 {
+        return (a, 1, 0);
+    }
+    let (g, x, y) = ext_gcd(b, a % b);
+    (g, y, x - a / b * y)
+}
+
+/// The range of `k` for which `0 <= c + k * m <= limit`, if it is not empty.
+fn steps(c: i64, m: i64, limit: i64) -> Option<(i64, i64)> {
+    // with a limit of `i64::MAX` the bounds do not fit into an i64
+    let (c, m, limit) = (i128::from(c), i128::from(m), i128::from(limit));
+    let (low, high) = match m.signum() {
+        0 => return (0..=limit).contains(&c).then_some((i64::MIN, i64::MAX)),
+        // bounds of `k * |m|`
+        1 => (-c, limit - c),
+        _ => (c - limit, c),
+    };
+    let m = m.abs();
+    let (low, high) = (-(-low).div_euclid(m), high.div_euclid(m));
+    let clamp = |k: i128| k.clamp(i64::MIN.into(), i64::MAX.into()) as i64;
+    (low <= high).then(|| (clamp(low), clamp(high)))
+}
+
+/// The tokens needed to win every prize that can be won with at most `limit`
+/// presses of each button, after moving the prizes by `offset` on both axes.
+pub(crate) fn tokens(games: &[Game], offset: i64, limit: i64) -> i64 {
+    games
+        .iter()
+        .map(|game| Game {
+            target: (game.target.0 + offset, game.target.1 + offset),
+            ..*game
+        })
+        .filter_map(|game| game.presses(limit))
+        .map(|(u, v)| u * 3 + v)
+        .sum()
+}
+
 use aoc_traits::AdventOfCodeDay;
 use nom::{bytes::complete::tag, character::complete::newline, IResult};
+use parsing::finish;
 pub struct Solver;
+impl Solver {
+    pub fn try_parse_input(input: &str) -> Result<Vec<Game>, eyre::Report> {
+        finish(input, parse_games(input))
+    }
+}
+
 impl AdventOfCodeDay for Solver {
     type ParsedInput<'a> = Vec<Game>;
     type Part1Output = i64;
     type Part2Output = i64;
 
     fn parse_input(input: &str) -> Self::ParsedInput<'_> {
-        parse_games(input).unwrap().1
+        Self::try_parse_input(input).expect("Failed to parse input")
     }
 
     fn solve_part1(input: &Self::ParsedInput<'_>) -> Self::Part1Output {
-        input.iter().map(|game| game.solve().unwrap_or(0)).sum()
+        tokens(input, 0, 100)
     }
     fn solve_part2(input: &Self::ParsedInput<'_>) -> Self::Part2Output {
-        input
-            .iter()
-            .map(|game| {
-                let Game {
-                    a_vec,
-                    b_vec,
-                    target: (target_x, target_y),
-                } = *game;
-                Game {
-                    a_vec,
-                    b_vec,
-                    target: (target_x + 10000000000000, target_y + 10000000000000),
-                }
-            })
-            .map(|game| game.solve().unwrap_or(0))
-            .sum()
+        tokens(input, 10000000000000, i64::MAX)
     }
 }
 
@@ -131,4 +202,20 @@ Prize: X=18641, Y=10279";
         let parsed = Solver::parse_input(TEST_INPUT);
         assert_eq!(Solver::solve_part2(&parsed), 875318608908);
     }
+
+    #[test]
+    fn test_degenerate_buttons() {
+        // A does not move along X
+        let parsed =
+            Solver::parse_input("Button A: X+0, Y+5\nButton B: X+3, Y+4\nPrize: X=30, Y=60");
+        assert_eq!(Solver::solve_part1(&parsed), 4 * 3 + 10);
+
+        // parallel buttons, B is cheaper per step but pressing it 101 times
+        // is above the limit
+        let parsed =
+            Solver::parse_input("Button A: X+1, Y+2\nButton B: X+3, Y+6\nPrize: X=303, Y=606");
+        assert_eq!(Solver::solve_part1(&parsed), 3 * 3 + 100);
+        let parsed = Solver::parse_input("Button A: X+0, Y+0\nButton B: X+0, Y+0\nPrize: X=0, Y=1");
+        assert_eq!(Solver::solve_part1(&parsed), 0);
+    }
 }
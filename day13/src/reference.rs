@@ -0,0 +1,119 @@
+//! Slow but obviously correct solutions to check [`crate::Solver`] against.
+
+struct Machine {
+    a: (i64, i64),
+    b: (i64, i64),
+    prize: (i64, i64),
+}
+
+fn parse(input: &str) -> Vec<Machine> {
+    let numbers = |line: &str| {
+        let (_, values) = line.split_once(": ").unwrap();
+        let (x, y) = values.split_once(", ").unwrap();
+        (x[2..].parse().unwrap(), y[2..].parse().unwrap())
+    };
+    input
+        .split("\n\n")
+        .map(|machine| {
+            let lines: Vec<&str> = machine.lines().collect();
+            Machine {
+                a: numbers(lines[0]),
+                b: numbers(lines[1]),
+                prize: numbers(lines[2]),
+            }
+        })
+        .collect()
+}
+
+/// Tries every combination of up to 100 presses per button.
+pub fn solve_part1(input: &str) -> i64 {
+    parse(input)
+        .iter()
+        .filter_map(|m| {
+            (0..=100)
+                .flat_map(|a| (0..=100).map(move |b| (a, b)))
+                .filter(|(a, b)| (a * m.a.0 + b * m.b.0, a * m.a.1 + b * m.b.1) == m.prize)
+                .map(|(a, b)| 3 * a + b)
+                .min()
+        })
+        .sum()
+}
+
+/// Tries every number of presses of A, with no limit on the presses, after
+/// moving the prizes by `offset` on both axes.
+///
+/// Part 2 moves them by 10000000000000, which is far too much to try, so this
+/// checks the same unlimited search on small offsets.
+pub fn solve_unlimited(input: &str, offset: i64) -> i64 {
+    parse(input)
+        .iter()
+        .filter_map(|m| {
+            let prize = (m.prize.0 + offset, m.prize.1 + offset);
+            // pressing A more often overshoots on an axis it moves along,
+            // and if it does not move at all pressing it never helps
+            let most = [(m.a.0, prize.0), (m.a.1, prize.1)]
+                .iter()
+                .filter(|&&(step, _)| step > 0)
+                .map(|&(step, prize)| prize / step)
+                .min()
+                .unwrap_or(0);
+            (0..=most)
+                .filter_map(|a| {
+                    let rest = (prize.0 - a * m.a.0, prize.1 - a * m.a.1);
+                    let b = match m.b {
+                        (0, 0) => 0,
+                        (0, by) => rest.1 / by,
+                        (bx, _) => rest.0 / bx,
+                    };
+                    (b >= 0 && (b * m.b.0, b * m.b.1) == rest).then_some(3 * a + b)
+                })
+                .min()
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_traits::AdventOfCodeDay;
+    use proptest::prelude::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::{gen, tokens, Solver};
+
+    proptest! {
+        #[test]
+        fn test_against_reference(seed: u64, size in 1usize..20, offset in 0i64..5000) {
+            let input = gen::generate(&mut StdRng::seed_from_u64(seed), size);
+            let parsed = Solver::parse_input(&input);
+            prop_assert_eq!(Solver::solve_part1(&parsed), super::solve_part1(&input));
+            // part 2 with an offset small enough to try every press
+            prop_assert_eq!(
+                tokens(&parsed, offset, i64::MAX),
+                super::solve_unlimited(&input, offset)
+            );
+        }
+
+        #[test]
+        fn test_parallel_buttons_against_reference(
+            (dx, dy) in (1i64..10, 1i64..10),
+            (a, b) in (0i64..5, 0i64..5),
+            (u, v) in (0i64..120, 0i64..120),
+            off_line: bool,
+        ) {
+            // the prize is on the line of the buttons unless `off_line`
+            let t = u * a + v * b;
+            let input = format!(
+                "Button A: X+{}, Y+{}\nButton B: X+{}, Y+{}\nPrize: X={}, Y={}",
+                a * dx,
+                a * dy,
+                b * dx,
+                b * dy,
+                t * dx + i64::from(off_line),
+                t * dy,
+            );
+            let parsed = Solver::parse_input(&input);
+            prop_assert_eq!(Solver::solve_part1(&parsed), super::solve_part1(&input));
+            prop_assert_eq!(tokens(&parsed, 0, i64::MAX), super::solve_unlimited(&input, 0));
+        }
+    }
+}
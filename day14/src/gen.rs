@@ -0,0 +1,81 @@
+//! Random inputs in the shape of the puzzle input.
+
+use std::fmt::Write;
+
+use rand::{seq::SliceRandom, Rng};
+
+use crate::Params;
+
+/// Number of robots in the real puzzle input.
+pub const DEFAULT_SIZE: usize = 500;
+
+/// Generates `size` robots inside the default [`Params`] area.
+pub fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+    generate_with(rng, size, &Params::default())
+}
+
+/// Tiles of a framed Christmas tree about a third of the size of the area.
+fn tree(params: &Params) -> Vec<(i64, i64)> {
+    let (width, height) = ((params.width / 3) | 1, params.height / 3);
+    let mut tiles = Vec::new();
+    for y in 0..height {
+        for x in 0..width {
+            if x == 0 || y == 0 || x == width - 1 || y == height - 1 {
+                tiles.push((x, y));
+            }
+        }
+    }
+    let center = width / 2;
+    for row in 0..(height - 1) / 2 {
+        for x in center - row.min(center - 1)..=center + row.min(center - 1) {
+            tiles.push((x, row + 1));
+        }
+    }
+    tiles
+}
+
+/// Generates `size` robots inside the area of `params`.
+///
+/// Like in the puzzle, the robots line up to a picture of a Christmas tree at
+/// a random second, and the remaining robots move around it at random. At that
+/// second no two robots share a tile, unless there are more robots than tiles.
+/// With fewer robots than tiles of the picture only a part of the tree is
+/// drawn.
+pub fn generate_with<R: Rng>(rng: &mut R, size: usize, params: &Params) -> String {
+    let Params { width, height, .. } = *params;
+    let second = rng.gen_range(0..width * height);
+    let offset = (rng.gen_range(0..width), rng.gen_range(0..height));
+    let mut taken = vec![false; (width * height) as usize];
+    let mut take =
+        |(x, y): (i64, i64)| !std::mem::replace(&mut taken[(y * width + x) as usize], true);
+    let mut targets: Vec<_> = tree(params)
+        .into_iter()
+        .map(|(x, y)| ((x + offset.0) % width, (y + offset.1) % height))
+        .filter(|&tile| take(tile))
+        .take(size)
+        .collect();
+    let mut free: Vec<_> = (0..height)
+        .flat_map(|y| (0..width).map(move |x| (x, y)))
+        .filter(|&tile| take(tile))
+        .collect();
+    free.shuffle(rng);
+    targets.extend(free.into_iter().take(size - targets.len()));
+    while targets.len() < size {
+        targets.push((rng.gen_range(0..width), rng.gen_range(0..height)));
+    }
+    targets.shuffle(rng);
+
+    let mut out = String::new();
+    for (x, y) in targets {
+        let mut v = (0, 0);
+        while v == (0, 0) {
+            v = (rng.gen_range(-99..100), rng.gen_range(-99..100));
+        }
+        let pos = (
+            (x - v.0 * second).rem_euclid(width),
+            (y - v.1 * second).rem_euclid(height),
+        );
+        writeln!(out, "p={},{} v={},{}", pos.0, pos.1, v.0, v.1).unwrap();
+    }
+    out
+}
@@ -1,3 +1,8 @@
+pub mod gen;
+#[cfg(feature = "reference")]
+pub mod reference;
+pub mod vis;
+
 #[derive(Debug)]
 pub struct Game {
     pos: (i64, i64),
@@ -43,6 +48,39 @@ impl Game {
     }
 }
 
+/// The most robots on a single line at each second in `0..period`.
+///
+/// `robots` holds the position and velocity of each robot along one axis.
+fn crowds(robots: &[(i64, i64)], period: i64) -> Vec<usize> {
+    let crowd = |lines: &mut Vec<usize>, i: i64| {
+        lines.fill(0);
+        for (pos, velocity) in robots {
+            lines[(pos + velocity * i).rem_euclid(period) as usize] += 1;
+        }
+        lines.iter().copied().max().unwrap_or(0)
+    };
+    let new_lines = || vec![0; period as usize];
+    #[cfg(feature = "parallel")]
+    let crowds = (0..period)
+        .into_par_iter()
+        .map_init(new_lines, crowd)
+        .collect();
+    #[cfg(not(feature = "parallel"))]
+    let crowds = {
+        let mut lines = new_lines();
+        (0..period).map(|i| crowd(&mut lines, i)).collect()
+    };
+    crowds
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
 use aoc_traits::AdventOfCodeDay;
 use nom::{
     bytes::complete::tag,
@@ -50,59 +88,99 @@ use nom::{
     sequence::{preceded, separated_pair},
     IResult,
 };
+use parsing::finish;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::cmp::Reverse;
+/// Puzzle parameters that differ between the example and the real input.
+#[derive(Debug, Clone)]
+pub struct Params {
+    pub width: i64,
+    pub height: i64,
+    /// Seconds simulated in part 1.
+    pub steps: i64,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            width: 101,
+            height: 103,
+            steps: 100,
+        }
+    }
+}
+
+impl Params {
+    /// Overrides a single parameter by name.
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), eyre::Report> {
+        match key {
+            "width" => self.width = positive(key, value)?,
+            "height" => self.height = positive(key, value)?,
+            "steps" => self.steps = value.parse()?,
+            _ => eyre::bail!("unknown parameter {key}, expected width, height or steps"),
+        }
+        Ok(())
+    }
+}
+
+/// Parses the size of the area, which has at least one tile per side.
+fn positive(key: &str, value: &str) -> Result<i64, eyre::Report> {
+    let value = value.parse()?;
+    eyre::ensure!(value > 0, "{key} must be at least 1, got {value}");
+    Ok(value)
+}
+
 pub struct Solver;
+impl Solver {
+    pub fn try_parse_input(input: &str) -> Result<Vec<Game>, eyre::Report> {
+        finish(input, parse_games(input))
+    }
+    pub fn solve_part1_with(input: &[Game], params: &Params) -> i64 {
+        let mut quadrants = [0; 5];
+        input.iter().for_each(|x| {
+            quadrants[x.solve_part1((params.width, params.height), params.steps)] += 1;
+        });
+        quadrants.iter().take(4).product()
+    }
+    /// The first second at which the robots draw a Christmas tree.
+    ///
+    /// The x coordinates repeat every `width` seconds and the y coordinates
+    /// every `height` seconds. The tree has a frame, so at the right phase of
+    /// each cycle unusually many robots share a column or a row. The crowds of
+    /// both cycles are counted once and added up for every second until the
+    /// positions repeat, which for coprime `width` and `height` is the second
+    /// the Chinese remainder theorem gives for the two densest phases.
+    pub fn solve_part2_with(input: &[Game], params: &Params) -> i64 {
+        let (width, height) = (params.width, params.height);
+        let columns: Vec<_> = input.iter().map(|x| (x.pos.0, x.velocity.0)).collect();
+        let rows: Vec<_> = input.iter().map(|x| (x.pos.1, x.velocity.1)).collect();
+        let (columns, rows) = (crowds(&columns, width), crowds(&rows, height));
+        let period = width / gcd(width, height) * height;
+        (0..period)
+            .max_by_key(|&i| {
+                let crowd = columns[(i % width) as usize] + rows[(i % height) as usize];
+                // ties go to the earlier second
+                (crowd, Reverse(i))
+            })
+            .unwrap_or(0)
+    }
+}
+
 impl AdventOfCodeDay for Solver {
     type ParsedInput<'a> = Vec<Game>;
     type Part1Output = i64;
     type Part2Output = i64;
 
     fn parse_input(input: &str) -> Self::ParsedInput<'_> {
-        parse_games(input).unwrap().1
+        Self::try_parse_input(input).expect("Failed to parse input")
     }
 
     fn solve_part1(input: &Self::ParsedInput<'_>) -> Self::Part1Output {
-        let mut quadrants = [0; 5];
-        input.iter().for_each(|x| {
-            quadrants[x.solve_part1((101, 103), 100)] += 1;
-        });
-        quadrants.iter().take(4).product()
+        Self::solve_part1_with(input, &Params::default())
     }
     fn solve_part2(input: &Self::ParsedInput<'_>) -> Self::Part2Output {
-        let grid = (101, 103);
-        let mut best = 0;
-        let mut best_i = 0;
-        for i in 0..101 * 103 {
-            let pos = input.iter().map(|x| x.pos_at(grid, i)).collect::<Vec<_>>();
-            let mut max_line = 0;
-            for x in 0..grid.0 {
-                let mut diag = 0;
-                for i in 0..grid.1 {
-                    if pos.contains(&(x, i)) {
-                        diag += 1;
-                    }
-                }
-                max_line = max_line.max(diag);
-            }
-            if max_line > best {
-                best = max_line;
-                best_i = i;
-            }
-        }
-        // let i = best_i;
-        // let pos = input.iter().map(|x| x.pos_at(grid, i)).collect::<Vec<_>>();
-        // println!("{}", i);
-        // for y in 0..grid.1 {
-        //     for x in 0..grid.0 {
-        //         if pos.contains(&(x, y)) {
-        //             print!("#");
-        //         } else {
-        //             print!(".");
-        //         }
-        //     }
-        //     println!();
-        // }
-        // println!("----------------------------------------------------------------------------------------------------------------------");
-        best_i
+        Self::solve_part2_with(input, &Params::default())
     }
 }
 
@@ -110,7 +188,7 @@ impl AdventOfCodeDay for Solver {
 mod tests {
     use aoc_traits::AdventOfCodeDay;
 
-    use crate::Solver;
+    use crate::{Params, Solver};
     const TEST_INPUT: &str = "p=0,4 v=3,-3
 p=6,3 v=-1,-3
 p=10,3 v=-1,2
@@ -127,11 +205,27 @@ p=9,5 v=-3,-3";
     #[test]
     fn test_stage1() {
         let parsed = Solver::parse_input(TEST_INPUT);
-        let mut quadrants = [0; 5];
-        parsed.iter().for_each(|x| {
-            quadrants[x.solve_part1((11, 7), 100)] += 1;
-        });
-        let res: usize = quadrants.iter().take(4).product();
-        assert_eq!(res, 12);
+        let params = Params {
+            width: 11,
+            height: 7,
+            steps: 100,
+        };
+        assert_eq!(Solver::solve_part1_with(&parsed, &params), 12);
+    }
+
+    #[test]
+    fn test_params() {
+        let mut params = Params::default();
+        params.set("width", "10").unwrap();
+        params.set("height", "10").unwrap();
+        // not coprime, the positions repeat after 10 seconds
+        let parsed = Solver::parse_input(TEST_INPUT);
+        assert_eq!(Solver::solve_part2_with(&parsed, &params), 6);
+
+        let error = params.set("width", "0").unwrap_err();
+        assert_eq!(error.to_string(), "width must be at least 1, got 0");
+        assert!(params.set("height", "-3").is_err());
+        assert!(params.set("depth", "3").is_err());
+        assert_eq!(params.width, 10);
     }
 }
@@ -0,0 +1,102 @@
+//! Slow but obviously correct solutions to check [`crate::Solver`] against.
+
+use crate::Params;
+
+fn parse(input: &str) -> Vec<((i64, i64), (i64, i64))> {
+    let pair = |s: &str| {
+        let (x, y) = s[2..].split_once(',').unwrap();
+        (x.parse().unwrap(), y.parse().unwrap())
+    };
+    input
+        .lines()
+        .map(|line| {
+            let (p, v) = line.split_once(' ').unwrap();
+            (pair(p), pair(v))
+        })
+        .collect()
+}
+
+/// Moves every robot one second at a time.
+pub fn solve_part1(input: &str, params: &Params) -> i64 {
+    let mut robots = parse(input);
+    for _ in 0..params.steps {
+        for (p, v) in &mut robots {
+            p.0 = (p.0 + v.0).rem_euclid(params.width);
+            p.1 = (p.1 + v.1).rem_euclid(params.height);
+        }
+    }
+    let (mid_x, mid_y) = (params.width / 2, params.height / 2);
+    let mut quadrants = [0; 4];
+    for ((x, y), _) in robots {
+        if x != mid_x && y != mid_y {
+            quadrants[usize::from(x > mid_x) + 2 * usize::from(y > mid_y)] += 1;
+        }
+    }
+    quadrants.iter().product()
+}
+
+/// The first second at which no two robots share a tile.
+///
+/// That is when the robots draw the Christmas tree, and every second until
+/// the positions repeat is tried.
+pub fn solve_part2(input: &str, params: &Params) -> i64 {
+    let mut robots = parse(input);
+    let (width, height) = (params.width as usize, params.height as usize);
+    for second in 0..params.width * params.height {
+        let mut occupied = vec![vec![false; width]; height];
+        let overlap = robots
+            .iter()
+            .any(|&((x, y), _)| std::mem::replace(&mut occupied[y as usize][x as usize], true));
+        if !overlap {
+            return second;
+        }
+        for (p, v) in &mut robots {
+            p.0 = (p.0 + v.0).rem_euclid(params.width);
+            p.1 = (p.1 + v.1).rem_euclid(params.height);
+        }
+    }
+    panic!("the robots never spread out to separate tiles");
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::{gen, Params, Solver};
+
+    proptest! {
+        #[test]
+        fn test_part1_against_reference(
+            seed: u64,
+            size in 1usize..100,
+            width in 3i64..30,
+            height in 3i64..30,
+        ) {
+            let params = Params { width, height, steps: 100 };
+            let input = gen::generate_with(&mut StdRng::seed_from_u64(seed), size, &params);
+            let parsed = Solver::try_parse_input(&input).unwrap();
+            prop_assert_eq!(
+                Solver::solve_part1_with(&parsed, &params),
+                super::solve_part1(&input, &params)
+            );
+        }
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(8))]
+
+        #[test]
+        fn test_part2_against_reference(seed: u64, noise in 0usize..50) {
+            // large enough for the tree to stand out in a single column, with
+            // enough robots that they practically never spread out by chance
+            let params = Params { width: 61, height: 63, steps: 100 };
+            let input = gen::generate_with(&mut StdRng::seed_from_u64(seed), 400 + noise, &params);
+            let parsed = Solver::try_parse_input(&input).unwrap();
+            prop_assert_eq!(
+                Solver::solve_part2_with(&parsed, &params),
+                super::solve_part2(&input, &params)
+            );
+        }
+    }
+}
@@ -0,0 +1,35 @@
+//! Frames of the robots moving around the bathroom.
+
+use grid::Grid;
+
+use crate::{Game, Params, Solver};
+
+/// The robots at `second`, with a `#` on every tile that has at least one.
+fn picture(input: &[Game], params: &Params, second: i64) -> Grid {
+    let mut grid = Grid::new(params.width as usize, params.height as usize, b'.');
+    for robot in input {
+        let (x, y) = robot.pos_at((params.width, params.height), second);
+        grid[(x as usize, y as usize)] = b'#';
+    }
+    grid
+}
+
+/// One frame per second until the robots have moved `params.steps` times.
+pub fn part1(input: &[Game], params: &Params, frame: &mut dyn FnMut(&Grid)) {
+    for second in 0..=params.steps {
+        frame(&picture(input, params, second));
+    }
+}
+
+/// The seconds up to the Christmas tree at which the robots crowd into a few
+/// columns or rows, the two cycles [`Solver::solve_part2_with`] combines.
+pub fn part2(input: &[Game], params: &Params, frame: &mut dyn FnMut(&Grid)) {
+    let tree = Solver::solve_part2_with(input, params);
+    for second in 0..=tree {
+        if second % params.width == tree % params.width
+            || second % params.height == tree % params.height
+        {
+            frame(&picture(input, params, second));
+        }
+    }
+}
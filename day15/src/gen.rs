@@ -0,0 +1,36 @@
+//! Random inputs in the shape of the puzzle input.
+
+use grid::Grid;
+use rand::{seq::SliceRandom, Rng};
+
+/// Side length of the real puzzle input.
+pub const DEFAULT_SIZE: usize = 50;
+
+/// Generates a walled `size` by `size` warehouse full of boxes and the robot's
+/// moves, eight per tile of the warehouse.
+pub fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+    let size = size.max(3);
+    let mut grid = Grid::new(size, size, b'#');
+    for (x, y) in grid.positions().collect::<Vec<_>>() {
+        if x > 0 && y > 0 && x < size - 1 && y < size - 1 {
+            grid[(x, y)] = match rng.gen_range(0..100) {
+                0..=4 => b'#',
+                5..=34 => b'O',
+                _ => b'.',
+            };
+        }
+    }
+    let robot = (rng.gen_range(1..size - 1), rng.gen_range(1..size - 1));
+    grid[robot] = b'@';
+
+    let mut out = grid.to_string();
+    out.push('\n');
+    let moves: Vec<u8> = (0..8 * size * size)
+        .map(|_| *b"^>v<".choose(rng).unwrap())
+        .collect();
+    for line in moves.chunks(1000) {
+        out.push_str(std::str::from_utf8(line).unwrap());
+        out.push('\n');
+    }
+    out
+}
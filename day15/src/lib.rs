@@ -1,26 +1,27 @@
+pub mod gen;
+pub mod vis;
+
 use std::str::FromStr;
 
 use aoc_traits::AdventOfCodeDay;
+use grid::{Direction, Grid, Pos};
+use parsing::error_at;
 
 #[derive(Debug, Clone)]
-pub struct Grid {
-    x: usize,
-    y: usize,
-    grid: Vec<u8>,
-    player: (usize, usize),
+pub struct Warehouse {
+    grid: Grid,
+    player: Pos,
 }
 
 #[derive(Debug, Clone)]
-pub struct WideGrid {
-    x: usize,
-    _y: usize,
-    grid: Vec<u8>,
-    player: (usize, usize),
+pub struct WideWarehouse {
+    grid: Grid,
+    player: Pos,
 }
 
 #[derive(Debug, Clone)]
 pub struct Game {
-    grid: Grid,
+    warehouse: Warehouse,
     instructions: Vec<Direction>,
 }
 
@@ -28,72 +29,51 @@ impl FromStr for Game {
     type Err = eyre::Report;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut player = None;
-
-        let x = s
-            .lines()
-            .next()
-            .ok_or_else(|| eyre::eyre!("empty input"))?
-            .len();
-        let mut gy = 0;
-
-        let mut grid = Vec::with_capacity(x * x);
-        let mut is_program = false;
-        let mut program = Vec::with_capacity(x * x);
-        for (y, line) in s.lines().enumerate() {
-            if line.is_empty() {
-                is_program = true;
-                continue;
-            }
-            if !is_program {
-                gy += 1;
-                for (x, c) in line.chars().enumerate() {
-                    if c == '@' {
-                        player = Some((x, y));
-                    }
-                    grid.push(c as u8);
-                }
-            } else {
-                for c in line.chars() {
-                    program.push(match c {
-                        '^' => Direction::Up,
-                        'v' => Direction::Down,
-                        '<' => Direction::Left,
-                        '>' => Direction::Right,
-                        _ => unreachable!(),
-                    });
-                }
-            }
+        let (grid, program) = s.split_once("\n\n").ok_or_else(|| {
+            eyre::eyre!("expected a map and a program separated by an empty line")
+        })?;
+        if let Some((idx, c)) = grid
+            .char_indices()
+            .find(|(_, c)| !matches!(c, '#' | '.' | 'O' | '@' | '\n'))
+        {
+            return Err(error_at(s, &grid[idx..], format!("invalid tile {c:?}")));
         }
+        let grid: Grid = grid.parse()?;
+        grid.ensure_border(b'#')?;
+        let player = grid.marker(b'@')?;
+        let instructions = program
+            .char_indices()
+            .filter(|(_, c)| !c.is_ascii_whitespace())
+            .map(|(idx, c)| {
+                u8::try_from(c)
+                    .ok()
+                    .and_then(Direction::from_arrow)
+                    .ok_or_else(|| {
+                        error_at(s, &program[idx..], format!("invalid instruction {c:?}"))
+                    })
+            })
+            .collect::<Result<_, _>>()?;
 
         Ok(Game {
-            instructions: program,
-            grid: Grid {
-                x,
-                y: gy,
-                player: player.expect("have a player"),
-                grid,
-            },
+            instructions,
+            warehouse: Warehouse { grid, player },
         })
     }
 }
 
-impl Grid {
-    fn get(&self, (x, y): (usize, usize)) -> u8 {
-        self.grid[y * self.x + x]
-    }
-    fn move_if_possible(&mut self, (x, y): (usize, usize), dir: Direction) -> bool {
-        let target = dir.step((x, y));
-        let target_item = self.get(target);
+impl Warehouse {
+    fn move_if_possible(&mut self, pos: Pos, dir: Direction) -> bool {
+        let target = dir.step(pos);
+        let target_item = self.grid[target];
         match target_item {
             b'#' => false,
             b'.' => {
-                self.grid.swap(target.1 * self.x + target.0, y * self.x + x);
+                self.grid.swap(target, pos);
                 true
             }
             b'O' => {
                 if self.move_if_possible(target, dir) {
-                    self.grid.swap(target.1 * self.x + target.0, y * self.x + x);
+                    self.grid.swap(target, pos);
                     true
                 } else {
                     false
@@ -115,19 +95,14 @@ impl Grid {
     fn run_program(self, program: &[Direction]) -> Self {
         program
             .iter()
-            .fold(self, |grid, dir| grid.simulate_step(*dir))
+            .fold(self, |warehouse, dir| warehouse.simulate_step(*dir))
     }
     fn stone_values(&self) -> usize {
-        self.grid
-            .iter()
-            .enumerate()
-            .filter(|(_, &x)| x == b'O')
-            .map(|(idx, _)| (idx % self.x) + (idx / self.x) * 100)
-            .sum()
+        self.grid.find_all(&b'O').map(|(x, y)| x + y * 100).sum()
     }
-    fn make_wider(&self) -> WideGrid {
-        let mut new_grid = Vec::with_capacity(self.grid.len() * 2);
-        self.grid.iter().copied().for_each(|x| match x {
+    fn make_wider(&self) -> WideWarehouse {
+        let mut new_grid = Vec::with_capacity(self.grid.cells().len() * 2);
+        self.grid.cells().iter().copied().for_each(|x| match x {
             b'#' => {
                 new_grid.push(b'#');
                 new_grid.push(b'#');
@@ -146,22 +121,18 @@ impl Grid {
             }
             _ => unreachable!(),
         });
-        WideGrid {
-            x: self.x * 2,
-            _y: self.y,
+        WideWarehouse {
+            grid: Grid::from_vec(self.grid.width() * 2, self.grid.height(), new_grid)
+                .expect("every tile is doubled"),
             player: (self.player.0 * 2, self.player.1),
-            grid: new_grid,
         }
     }
 }
 
-impl WideGrid {
-    fn get(&self, (x, y): (usize, usize)) -> u8 {
-        self.grid[y * self.x + x]
-    }
-    fn check_if_move_possible(&self, (x, y): (usize, usize), dir: Direction) -> bool {
-        let target = dir.step((x, y));
-        let target_item = self.get(target);
+impl WideWarehouse {
+    fn check_if_move_possible(&self, pos: Pos, dir: Direction) -> bool {
+        let target = dir.step(pos);
+        let target_item = self.grid[target];
         match target_item {
             b'#' => false,
             b'.' => true,
@@ -176,19 +147,19 @@ impl WideGrid {
             _ => unreachable!(),
         }
     }
-    fn move_if_possible(&mut self, (x, y): (usize, usize), dir: Direction) -> bool {
-        let target = dir.step((x, y));
-        let target_item = self.get(target);
+    fn move_if_possible(&mut self, pos: Pos, dir: Direction) -> bool {
+        let target = dir.step(pos);
+        let target_item = self.grid[target];
         match dir {
             Direction::Left | Direction::Right => match target_item {
                 b'#' => false,
                 b'.' => {
-                    self.grid.swap(target.1 * self.x + target.0, y * self.x + x);
+                    self.grid.swap(target, pos);
                     true
                 }
                 b'[' | b']' => {
                     if self.move_if_possible(target, dir) {
-                        self.grid.swap(target.1 * self.x + target.0, y * self.x + x);
+                        self.grid.swap(target, pos);
                         true
                     } else {
                         false
@@ -199,24 +170,24 @@ impl WideGrid {
             Direction::Up | Direction::Down => match target_item {
                 b'#' => false,
                 b'.' => {
-                    self.grid.swap(target.1 * self.x + target.0, y * self.x + x);
+                    self.grid.swap(target, pos);
                     true
                 }
                 b'[' => {
-                    if self.check_if_move_possible((x, y), dir) {
+                    if self.check_if_move_possible(pos, dir) {
                         assert!(self.move_if_possible(target, dir));
                         assert!(self.move_if_possible((target.0 + 1, target.1), dir));
-                        self.grid.swap(target.1 * self.x + target.0, y * self.x + x);
+                        self.grid.swap(target, pos);
                         true
                     } else {
                         false
                     }
                 }
                 b']' => {
-                    if self.check_if_move_possible((x, y), dir) {
+                    if self.check_if_move_possible(pos, dir) {
                         assert!(self.move_if_possible(target, dir));
                         assert!(self.move_if_possible((target.0 - 1, target.1), dir));
-                        self.grid.swap(target.1 * self.x + target.0, y * self.x + x);
+                        self.grid.swap(target, pos);
                         true
                     } else {
                         false
@@ -239,57 +210,38 @@ impl WideGrid {
     fn run_program(self, program: &[Direction]) -> Self {
         program
             .iter()
-            .fold(self, |grid, dir| grid.simulate_step(*dir))
+            .fold(self, |warehouse, dir| warehouse.simulate_step(*dir))
     }
     fn stone_values(&self) -> usize {
-        self.grid
-            .iter()
-            .enumerate()
-            .filter(|(_, &x)| x == b'[')
-            .map(|(idx, _)| (idx % self.x) + (idx / self.x) * 100)
-            .sum()
+        self.grid.find_all(&b'[').map(|(x, y)| x + y * 100).sum()
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-#[repr(u8)]
-enum Direction {
-    Up = 1,
-    Down = 2,
-    Left = 4,
-    Right = 8,
-}
-
-impl Direction {
-    fn step(&self, player: (usize, usize)) -> (usize, usize) {
-        match self {
-            Self::Up => (player.0, player.1 - 1),
-            Self::Left => (player.0 - 1, player.1),
-            Self::Down => (player.0, player.1 + 1),
-            Self::Right => (player.0 + 1, player.1),
-        }
+#[derive(Default)]
+pub struct Solver;
+impl Solver {
+    pub fn try_parse_input(input: &str) -> Result<Game, eyre::Report> {
+        input.parse()
     }
 }
 
-#[derive(Default)]
-pub struct Solver;
 impl AdventOfCodeDay for Solver {
     type ParsedInput<'a> = Game;
     type Part1Output = usize;
     type Part2Output = usize;
 
     fn parse_input(input: &str) -> Self::ParsedInput<'_> {
-        input.parse().expect("Failed to parse input")
+        Self::try_parse_input(input).expect("Failed to parse input")
     }
 
     fn solve_part1(input: &Self::ParsedInput<'_>) -> Self::Part1Output {
-        let grid = input.grid.clone().run_program(&input.instructions);
-        grid.stone_values()
+        let warehouse = input.warehouse.clone().run_program(&input.instructions);
+        warehouse.stone_values()
     }
     fn solve_part2(input: &Self::ParsedInput<'_>) -> Self::Part2Output {
-        let grid = input.grid.make_wider();
-        let grid = grid.run_program(&input.instructions);
-        grid.stone_values()
+        let warehouse = input.warehouse.make_wider();
+        let warehouse = warehouse.run_program(&input.instructions);
+        warehouse.stone_values()
     }
 }
 
@@ -342,4 +294,11 @@ v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^";
         let parsed = Solver::parse_input(TEST_INPUT);
         assert_eq!(Solver::solve_part2(&parsed), 9021);
     }
+    #[test]
+    fn test_parse_errors() {
+        let error = Solver::try_parse_input("#####\n#@.x#\n#####\n\n<").unwrap_err();
+        assert_eq!(error.to_string(), "line 2, column 4: invalid tile 'x'");
+        // only the narrow warehouse is parsed, the wide one is derived from it
+        assert!(Solver::try_parse_input("######\n#@[].#\n######\n\n<").is_err());
+    }
 }
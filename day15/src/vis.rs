@@ -0,0 +1,25 @@
+//! Frames of the robot pushing boxes around the warehouse.
+
+use grid::Grid;
+
+use crate::Game;
+
+/// The warehouse after every move of the robot.
+pub fn part1(input: &Game, frame: &mut dyn FnMut(&Grid)) {
+    let mut warehouse = input.warehouse.clone();
+    frame(&warehouse.grid);
+    for &dir in &input.instructions {
+        warehouse = warehouse.simulate_step(dir);
+        frame(&warehouse.grid);
+    }
+}
+
+/// The twice as wide warehouse after every move of the robot.
+pub fn part2(input: &Game, frame: &mut dyn FnMut(&Grid)) {
+    let mut warehouse = input.warehouse.make_wider();
+    frame(&warehouse.grid);
+    for &dir in &input.instructions {
+        warehouse = warehouse.simulate_step(dir);
+        frame(&warehouse.grid);
+    }
+}
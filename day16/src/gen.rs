@@ -0,0 +1,18 @@
+//! Random inputs in the shape of the puzzle input.
+
+use rand::Rng;
+
+/// Side length of the real puzzle input.
+pub const DEFAULT_SIZE: usize = 141;
+
+/// Generates a `size` by `size` maze with the start in the bottom left and the
+/// end in the top right corner.
+///
+/// Some walls are knocked out so that there are several best paths to find.
+pub fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+    let mut grid = grid::gen::maze(rng, size.max(5), size.max(5), 0.05);
+    let (width, height) = (grid.width(), grid.height());
+    grid[(1, height - 2)] = b'S';
+    grid[(width - 2, 1)] = b'E';
+    grid.to_string()
+}
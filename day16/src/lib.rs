@@ -1,76 +1,62 @@
-use std::{collections::VecDeque, str::FromStr, vec};
+pub mod gen;
+pub mod vis;
+
+use std::{collections::VecDeque, str::FromStr};
 
 use aoc_traits::AdventOfCodeDay;
+use grid::{Direction, Grid, Pos};
 use rustc_hash::FxHashSet;
 
 #[derive(Debug, Clone)]
-pub struct Grid {
-    x: usize,
-    y: usize,
-    grid: Vec<u8>,
+pub struct Maze {
+    grid: Grid,
+    start: Pos,
+    end: Pos,
 }
 
 #[derive(Debug, Clone)]
-pub struct ScoredGrid {
-    score: Vec<[usize; 4]>,
-    grid: Grid,
+pub struct ScoredMaze {
+    score: Grid<[usize; 4]>,
+    maze: Maze,
 }
 
-impl FromStr for Grid {
+impl FromStr for Maze {
     type Err = eyre::Report;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let x = s
-            .lines()
-            .next()
-            .ok_or_else(|| eyre::eyre!("empty input"))?
-            .len();
-        let mut gy = 0;
-
-        let mut grid = Vec::with_capacity(x * x);
-        for line in s.lines() {
-            gy += 1;
-            for c in line.chars() {
-                grid.push(c as u8);
-            }
-        }
-
-        Ok(Grid { x, y: gy, grid })
+        let grid: Grid = s.parse()?;
+        grid.ensure_border(b'#')?;
+        let start = grid.marker(b'S')?;
+        let end = grid.marker(b'E')?;
+        Ok(Maze { grid, start, end })
     }
 }
 
-impl Grid {
-    fn idx(&self, (x, y): (usize, usize)) -> usize {
-        y * self.x + x
-    }
-    fn get(&self, (x, y): (usize, usize)) -> u8 {
-        self.grid[self.idx((x, y))]
-    }
-
-    fn find_best_path(&self) -> Vec<[usize; 4]> {
-        let start = (1, self.y - 2);
-        let mut score = vec![[std::usize::MAX; 4]; self.grid.len()];
+impl Maze {
+    fn find_best_path(&self) -> Grid<[usize; 4]> {
+        let start = self.start;
+        let mut score = Grid::new(self.grid.width(), self.grid.height(), [usize::MAX; 4]);
         let mut to_visit = VecDeque::new();
         to_visit.push_back((start, Direction::Right, 0));
 
         while let Some((idx, dir, incoming_score)) = to_visit.pop_front() {
             let next_idx = dir.step(idx);
-            let next_tile = self.get(dir.step(idx));
+            let next_tile = self.grid[next_idx];
             let next_score = incoming_score + 1;
 
             let left_score = incoming_score + 1000;
             let left_dir = dir.turn_left();
-            if incoming_score >= score[self.idx(next_idx)][dir as usize] {
+            if incoming_score >= score[next_idx][dir as usize] {
                 continue;
             }
-            if left_score < score[self.idx(idx)][left_dir as usize] {
-                score[self.idx(idx)][left_dir as usize] = left_score;
+            if left_score < score[idx][left_dir as usize] {
+                score[idx][left_dir as usize] = left_score;
                 to_visit.push_back((idx, left_dir, left_score));
             }
             let right_score = incoming_score + 1000;
             let right_dir = dir.turn_right();
-            if right_score < score[self.idx(idx)][right_dir as usize] {
-                score[self.idx(idx)][right_dir as usize] = right_score;
+            if right_score < score[idx][right_dir as usize] {
+                score[idx][right_dir as usize] = right_score;
                 to_visit.push_back((idx, right_dir, right_score));
             }
 
@@ -78,8 +64,8 @@ impl Grid {
                 continue;
             }
 
-            if next_score < score[self.idx(next_idx)][dir as usize] {
-                score[self.idx(next_idx)][dir as usize] = next_score;
+            if next_score < score[next_idx][dir as usize] {
+                score[next_idx][dir as usize] = next_score;
                 to_visit.push_back((next_idx, dir, next_score));
             }
         }
@@ -87,46 +73,31 @@ impl Grid {
     }
 }
 
-impl ScoredGrid {
+impl ScoredMaze {
     fn get_score(&self) -> usize {
-        let end = (self.grid.x - 2, 1);
-        self.score[self.grid.idx(end)]
-            .iter()
-            .copied()
-            .min()
-            .unwrap()
+        self.score[self.maze.end].iter().copied().min().unwrap()
     }
-    fn find_best_path_tiles(&self) -> usize {
-        let end = (self.grid.x - 2, 1);
+    /// Calls `visit` for every tile on any of the best paths, walking back
+    /// from the end. Tiles can be visited more than once.
+    fn walk_best_paths(&self, mut visit: impl FnMut(Pos)) {
+        let end = self.maze.end;
 
         let mut to_visit = VecDeque::new();
         // now walk back from the end
-        let min_score = self.score[self.grid.idx(end)]
-            .iter()
-            .copied()
-            .min()
-            .unwrap();
-        if self.score[self.grid.idx(end)][Direction::Up as usize] == min_score {
-            to_visit.push_back((end, Direction::Up, min_score));
-        }
-        if self.score[self.grid.idx(end)][Direction::Down as usize] == min_score {
-            to_visit.push_back((end, Direction::Down, min_score));
-        }
-        if self.score[self.grid.idx(end)][Direction::Left as usize] == min_score {
-            to_visit.push_back((end, Direction::Left, min_score));
-        }
-        if self.score[self.grid.idx(end)][Direction::Right as usize] == min_score {
-            to_visit.push_back((end, Direction::Right, min_score));
+        let min_score = self.score[end].iter().copied().min().unwrap();
+        for dir in Direction::ALL {
+            if self.score[end][dir as usize] == min_score {
+                to_visit.push_back((end, dir, min_score));
+            }
         }
-        let mut points_on_path = FxHashSet::default();
         while let Some((idx, dir, incoming_score)) = to_visit.pop_front() {
-            points_on_path.insert(idx);
+            visit(idx);
 
             if incoming_score == 0 {
                 continue;
             }
-            let next_idx = dir.step_back(idx);
-            if self.score[self.grid.idx(next_idx)][dir as usize] == incoming_score - 1 {
+            let next_idx = dir.reverse().step(idx);
+            if self.score[next_idx][dir as usize] == incoming_score - 1 {
                 to_visit.push_back((next_idx, dir, incoming_score - 1));
             }
             if incoming_score < 1000 {
@@ -134,74 +105,42 @@ impl ScoredGrid {
             }
             let left_dir = dir.turn_right();
             let right_dir = dir.turn_left();
-            if self.score[self.grid.idx(idx)][left_dir as usize] == incoming_score - 1000 {
+            if self.score[idx][left_dir as usize] == incoming_score - 1000 {
                 to_visit.push_back((idx, left_dir, incoming_score - 1000));
             }
-            if self.score[self.grid.idx(idx)][right_dir as usize] == incoming_score - 1000 {
+            if self.score[idx][right_dir as usize] == incoming_score - 1000 {
                 to_visit.push_back((idx, right_dir, incoming_score - 1000));
             }
         }
+    }
+    fn find_best_path_tiles(&self) -> usize {
+        let mut points_on_path = FxHashSet::default();
+        self.walk_best_paths(|pos| {
+            points_on_path.insert(pos);
+        });
         points_on_path.len()
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-#[repr(u8)]
-enum Direction {
-    Up = 0,
-    Down = 1,
-    Left = 2,
-    Right = 3,
-}
-
-impl Direction {
-    fn step(&self, player: (usize, usize)) -> (usize, usize) {
-        match self {
-            Self::Up => (player.0, player.1 - 1),
-            Self::Left => (player.0 - 1, player.1),
-            Self::Down => (player.0, player.1 + 1),
-            Self::Right => (player.0 + 1, player.1),
-        }
-    }
-    fn step_back(&self, player: (usize, usize)) -> (usize, usize) {
-        match self {
-            Self::Up => (player.0, player.1 + 1),
-            Self::Left => (player.0 + 1, player.1),
-            Self::Down => (player.0, player.1 - 1),
-            Self::Right => (player.0 - 1, player.1),
-        }
-    }
-    fn turn_left(&self) -> Self {
-        match self {
-            Self::Up => Self::Left,
-            Self::Left => Self::Down,
-            Self::Down => Self::Right,
-            Self::Right => Self::Up,
-        }
-    }
-    fn turn_right(&self) -> Self {
-        match self {
-            Self::Up => Self::Right,
-            Self::Right => Self::Down,
-            Self::Down => Self::Left,
-            Self::Left => Self::Up,
-        }
+#[derive(Default)]
+pub struct Solver;
+impl Solver {
+    pub fn try_parse_input(input: &str) -> Result<ScoredMaze, eyre::Report> {
+        let maze: Maze = input.parse()?;
+        Ok(ScoredMaze {
+            score: maze.find_best_path(),
+            maze,
+        })
     }
 }
 
-#[derive(Default)]
-pub struct Solver;
 impl AdventOfCodeDay for Solver {
-    type ParsedInput<'a> = ScoredGrid;
+    type ParsedInput<'a> = ScoredMaze;
     type Part1Output = usize;
     type Part2Output = usize;
 
     fn parse_input(input: &str) -> Self::ParsedInput<'_> {
-        let grid: Grid = input.parse().expect("Failed to parse input");
-        ScoredGrid {
-            score: grid.find_best_path(),
-            grid,
-        }
+        Self::try_parse_input(input).expect("Failed to parse input")
     }
 
     fn solve_part1(input: &Self::ParsedInput<'_>) -> Self::Part1Output {
@@ -264,4 +203,9 @@ mod tests {
         let parsed = Solver::parse_input(TEST_INPUT);
         assert_eq!(Solver::solve_part2(&parsed), 64);
     }
+    #[test]
+    fn test_parse_errors() {
+        let err = Solver::try_parse_input("#####\n#S.E.\n#####\n").unwrap_err();
+        assert!(err.to_string().starts_with("expected '#' around the grid"));
+    }
 }
@@ -0,0 +1,37 @@
+//! Frames of the search for the best paths through the maze.
+
+use grid::{Grid, Pos};
+
+use crate::ScoredMaze;
+
+/// The search spreading out from the start, one frame per tile in the order of
+/// the lowest score it can be reached with.
+pub fn part1(input: &ScoredMaze, frame: &mut dyn FnMut(&Grid)) {
+    let mut grid = input.maze.grid.clone();
+    let mut reached: Vec<(usize, Pos)> = input
+        .score
+        .iter()
+        .filter(|&(pos, _)| grid[pos] == b'.')
+        .map(|(pos, score)| (score.iter().copied().min().unwrap(), pos))
+        .filter(|&(score, _)| score != usize::MAX)
+        .collect();
+    reached.sort();
+    frame(&grid);
+    for (_, pos) in reached {
+        grid[pos] = b'o';
+        frame(&grid);
+    }
+}
+
+/// The tiles on any of the best paths, drawn as `O` while walking back from
+/// the end.
+pub fn part2(input: &ScoredMaze, frame: &mut dyn FnMut(&Grid)) {
+    let mut grid = input.maze.grid.clone();
+    frame(&grid);
+    input.walk_best_paths(|pos| {
+        if grid[pos] == b'.' {
+            grid[pos] = b'O';
+            frame(&grid);
+        }
+    });
+}
@@ -0,0 +1,265 @@
+//! The instructions of the 3-bit computer and a disassembler for them.
+
+use std::fmt::{self, Display, Write};
+
+use eyre::bail;
+
+/// A register of the computer, the discriminant is its index in the register
+/// array.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Register {
+    A = 0,
+    B = 1,
+    C = 2,
+}
+
+impl Display for Register {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Register::A => "A",
+            Register::B => "B",
+            Register::C => "C",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A combo operand, either a literal 0 to 3 or the value of a register.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Combo {
+    Literal(u8),
+    Register(Register),
+}
+
+impl Combo {
+    pub fn decode(operand: u8) -> eyre::Result<Self> {
+        Ok(match operand {
+            0..=3 => Combo::Literal(operand),
+            4 => Combo::Register(Register::A),
+            5 => Combo::Register(Register::B),
+            6 => Combo::Register(Register::C),
+            7 => bail!("combo operand 7 is reserved"),
+            _ => bail!("{operand} is not a 3-bit operand"),
+        })
+    }
+
+    pub fn value(self, registers: &[u64; 3]) -> u64 {
+        match self {
+            Combo::Literal(literal) => literal.into(),
+            Combo::Register(register) => registers[register as usize],
+        }
+    }
+}
+
+impl Display for Combo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Combo::Literal(literal) => write!(f, "{literal}"),
+            Combo::Register(register) => write!(f, "{register}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    /// `A = A >> combo`
+    Adv(Combo),
+    /// `B = B ^ literal`
+    Bxl(u8),
+    /// `B = combo % 8`
+    Bst(Combo),
+    /// Jumps to `literal` unless A is 0.
+    Jnz(u8),
+    /// `B = B ^ C`, the operand is ignored.
+    Bxc,
+    /// Outputs `combo % 8`.
+    Out(Combo),
+    /// `B = A >> combo`
+    Bdv(Combo),
+    /// `C = A >> combo`
+    Cdv(Combo),
+}
+
+/// What happens after an instruction was executed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Flow {
+    Next,
+    Jump(usize),
+    Output(u8),
+}
+
+/// `value >> shift`, 0 once all bits are shifted out.
+pub fn shr(value: u64, shift: u64) -> u64 {
+    u32::try_from(shift)
+        .ok()
+        .and_then(|shift| value.checked_shr(shift))
+        .unwrap_or(0)
+}
+
+impl Instruction {
+    pub fn decode(opcode: u8, operand: u8) -> eyre::Result<Self> {
+        if operand > 7 {
+            bail!("{operand} is not a 3-bit operand");
+        }
+        Ok(match opcode {
+            0 => Instruction::Adv(Combo::decode(operand)?),
+            1 => Instruction::Bxl(operand),
+            2 => Instruction::Bst(Combo::decode(operand)?),
+            3 => Instruction::Jnz(operand),
+            4 => Instruction::Bxc,
+            5 => Instruction::Out(Combo::decode(operand)?),
+            6 => Instruction::Bdv(Combo::decode(operand)?),
+            7 => Instruction::Cdv(Combo::decode(operand)?),
+            _ => bail!("{opcode} is not a 3-bit opcode"),
+        })
+    }
+
+    pub fn opcode(self) -> u8 {
+        match self {
+            Instruction::Adv(_) => 0,
+            Instruction::Bxl(_) => 1,
+            Instruction::Bst(_) => 2,
+            Instruction::Jnz(_) => 3,
+            Instruction::Bxc => 4,
+            Instruction::Out(_) => 5,
+            Instruction::Bdv(_) => 6,
+            Instruction::Cdv(_) => 7,
+        }
+    }
+
+    pub fn mnemonic(self) -> &'static str {
+        match self {
+            Instruction::Adv(_) => "adv",
+            Instruction::Bxl(_) => "bxl",
+            Instruction::Bst(_) => "bst",
+            Instruction::Jnz(_) => "jnz",
+            Instruction::Bxc => "bxc",
+            Instruction::Out(_) => "out",
+            Instruction::Bdv(_) => "bdv",
+            Instruction::Cdv(_) => "cdv",
+        }
+    }
+
+    /// Executes the instruction on `registers`.
+    pub fn apply(self, registers: &mut [u64; 3]) -> Flow {
+        let [a, b, c] = *registers;
+        match self {
+            Instruction::Adv(combo) => registers[0] = shr(a, combo.value(registers)),
+            Instruction::Bxl(literal) => registers[1] = b ^ u64::from(literal),
+            Instruction::Bst(combo) => registers[1] = combo.value(registers) % 8,
+            Instruction::Jnz(target) if a != 0 => return Flow::Jump(target.into()),
+            Instruction::Jnz(_) => {}
+            Instruction::Bxc => registers[1] = b ^ c,
+            Instruction::Out(combo) => return Flow::Output((combo.value(registers) % 8) as u8),
+            Instruction::Bdv(combo) => registers[1] = shr(a, combo.value(registers)),
+            Instruction::Cdv(combo) => registers[2] = shr(a, combo.value(registers)),
+        }
+        Flow::Next
+    }
+
+    /// What the instruction does, e.g. `B = A % 8` for `bst A`.
+    pub fn effect(self) -> String {
+        match self {
+            Instruction::Adv(combo) => format!("A = A >> {combo}"),
+            Instruction::Bxl(literal) => format!("B = B ^ {literal}"),
+            Instruction::Bst(combo) => format!("B = {combo} % 8"),
+            Instruction::Jnz(target) => format!("if A != 0 jump to {target}"),
+            Instruction::Bxc => "B = B ^ C".to_string(),
+            Instruction::Out(combo) => format!("output {combo} % 8"),
+            Instruction::Bdv(combo) => format!("B = A >> {combo}"),
+            Instruction::Cdv(combo) => format!("C = A >> {combo}"),
+        }
+    }
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mnemonic = self.mnemonic();
+        match self {
+            Instruction::Adv(combo)
+            | Instruction::Bst(combo)
+            | Instruction::Out(combo)
+            | Instruction::Bdv(combo)
+            | Instruction::Cdv(combo) => write!(f, "{mnemonic} {combo}"),
+            Instruction::Bxl(literal) | Instruction::Jnz(literal) => {
+                write!(f, "{mnemonic} {literal}")
+            }
+            Instruction::Bxc => f.write_str(mnemonic),
+        }
+    }
+}
+
+/// One line of assembly per instruction, prefixed with its address.
+///
+/// Instructions are read at even addresses, as jumps in the puzzle inputs only
+/// ever go there. Pairs that are not a valid instruction are shown as `.data`
+/// with the reason.
+pub fn disassemble(inst: &[u8]) -> String {
+    let mut out = String::new();
+    for (idx, pair) in inst.chunks(2).enumerate() {
+        let ip = idx * 2;
+        let line = match *pair {
+            [opcode, operand] => match Instruction::decode(opcode, operand) {
+                Ok(instruction) => {
+                    format!("{:<10}; {}", instruction.to_string(), instruction.effect())
+                }
+                Err(e) => format!("{:<10}; {e}", format!(".data {opcode},{operand}")),
+            },
+            [opcode] => format!("{:<10}; missing operand", format!(".data {opcode}")),
+            _ => unreachable!("chunks of two"),
+        };
+        writeln!(out, "{ip:>3}: {line}").unwrap();
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{disassemble, Combo, Flow, Instruction, Register};
+
+    #[test]
+    fn test_instructions() {
+        for opcode in 0..8 {
+            for operand in 0..7 {
+                let instruction = Instruction::decode(opcode, operand).unwrap();
+                assert_eq!(instruction.opcode(), opcode);
+            }
+        }
+        assert!(Instruction::decode(0, 7).is_err());
+        assert_eq!(Instruction::decode(1, 7).unwrap(), Instruction::Bxl(7));
+        assert!(Instruction::decode(8, 0).is_err());
+        assert!(Instruction::decode(1, 8).is_err());
+
+        let mut registers = [10, 29, 2024];
+        let cdv = Instruction::Cdv(Combo::Register(Register::B));
+        assert_eq!(cdv.apply(&mut registers), Flow::Next);
+        assert_eq!(registers, [10, 29, 0]);
+        assert_eq!(
+            Instruction::Out(Combo::Register(Register::A)).apply(&mut registers),
+            Flow::Output(2)
+        );
+        assert_eq!(Instruction::Jnz(4).apply(&mut registers), Flow::Jump(4));
+        // shifting by a register that holds more than 63 clears the target
+        registers[1] = 100;
+        Instruction::Adv(Combo::Register(Register::B)).apply(&mut registers);
+        assert_eq!(registers[0], 0);
+        assert_eq!(Instruction::Jnz(4).apply(&mut registers), Flow::Next);
+    }
+
+    #[test]
+    fn test_disassemble() {
+        assert_eq!(
+            disassemble(&[2, 4, 1, 3, 7, 5, 0, 3, 4, 1, 5, 5, 3, 0, 6, 7, 2]),
+            "  0: bst A     ; B = A % 8
+  2: bxl 3     ; B = B ^ 3
+  4: cdv B     ; C = A >> B
+  6: adv 3     ; A = A >> 3
+  8: bxc       ; B = B ^ C
+ 10: out B     ; output B % 8
+ 12: jnz 0     ; if A != 0 jump to 0
+ 14: .data 6,7 ; combo operand 7 is reserved
+ 16: .data 2   ; missing operand
+"
+        );
+    }
+}
@@ -0,0 +1,253 @@
+//! Pseudo-code for programs that are a single loop, like all puzzle inputs.
+//!
+//! The body of the loop is executed symbolically, so that the output and the
+//! registers after an iteration are expressions over the registers at the
+//! start of it.
+
+use std::fmt::{self, Display};
+
+use eyre::{bail, ensure};
+
+use crate::asm::{shr, Combo, Instruction, Register};
+
+/// A value computed from the registers at the start of an iteration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expr {
+    Register(Register),
+    Literal(u64),
+    Shr(Box<Expr>, Box<Expr>),
+    Xor(Box<Expr>, Box<Expr>),
+    Mod8(Box<Expr>),
+}
+
+impl Expr {
+    fn combo(combo: Combo, registers: &[Expr; 3]) -> Expr {
+        match combo {
+            Combo::Literal(literal) => Expr::Literal(literal.into()),
+            Combo::Register(register) => registers[register as usize].clone(),
+        }
+    }
+
+    fn shr(value: Expr, shift: Expr) -> Expr {
+        match (value, shift) {
+            (Expr::Literal(value), Expr::Literal(shift)) => Expr::Literal(shr(value, shift)),
+            (value, Expr::Literal(0)) => value,
+            (value, shift) => Expr::Shr(Box::new(value), Box::new(shift)),
+        }
+    }
+
+    fn xor(lhs: Expr, rhs: Expr) -> Expr {
+        match (lhs, rhs) {
+            (Expr::Literal(lhs), Expr::Literal(rhs)) => Expr::Literal(lhs ^ rhs),
+            (value, Expr::Literal(0)) | (Expr::Literal(0), value) => value,
+            (lhs, rhs) => Expr::Xor(Box::new(lhs), Box::new(rhs)),
+        }
+    }
+
+    fn mod8(value: Expr) -> Expr {
+        match value {
+            Expr::Literal(value) => Expr::Literal(value % 8),
+            value if value.is_3_bit() => value,
+            value => Expr::Mod8(Box::new(value)),
+        }
+    }
+
+    /// Whether the value is always below 8.
+    fn is_3_bit(&self) -> bool {
+        match self {
+            Expr::Register(_) => false,
+            Expr::Literal(value) => *value < 8,
+            Expr::Shr(value, _) => value.is_3_bit(),
+            Expr::Xor(lhs, rhs) => lhs.is_3_bit() && rhs.is_3_bit(),
+            Expr::Mod8(_) => true,
+        }
+    }
+
+    /// The value for the given registers at the start of an iteration.
+    pub fn eval(&self, registers: &[u64; 3]) -> u64 {
+        match self {
+            Expr::Register(register) => registers[*register as usize],
+            Expr::Literal(value) => *value,
+            Expr::Shr(value, shift) => shr(value.eval(registers), shift.eval(registers)),
+            Expr::Xor(lhs, rhs) => lhs.eval(registers) ^ rhs.eval(registers),
+            Expr::Mod8(value) => value.eval(registers) % 8,
+        }
+    }
+
+    fn is_compound(&self) -> bool {
+        !matches!(self, Expr::Register(_) | Expr::Literal(_))
+    }
+}
+
+/// Writes `expr`, in parentheses unless it is a register or a literal.
+struct Operand<'a>(&'a Expr);
+
+impl Display for Operand<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0.is_compound() {
+            true => write!(f, "({})", self.0),
+            false => write!(f, "{}", self.0),
+        }
+    }
+}
+
+impl Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expr::Register(register) => write!(f, "{register}"),
+            Expr::Literal(value) => write!(f, "{value}"),
+            Expr::Shr(value, shift) => write!(f, "{} >> {}", Operand(value), Operand(shift)),
+            Expr::Xor(lhs, rhs) => write!(f, "{} ^ {}", Operand(lhs), Operand(rhs)),
+            Expr::Mod8(value) => write!(f, "{} % 8", Operand(value)),
+        }
+    }
+}
+
+/// A program that repeats its body until A is 0.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Loop {
+    /// The values output by one iteration, in order.
+    pub outputs: Vec<Expr>,
+    /// The registers at the end of an iteration.
+    pub registers: [Expr; 3],
+}
+
+impl Loop {
+    /// Decompiles a program whose only jump is a `jnz 0` at the end.
+    pub fn decompile(instructions: &[Instruction]) -> eyre::Result<Loop> {
+        let Some((Instruction::Jnz(0), body)) = instructions.split_last() else {
+            bail!("the program does not end with a jump to the start");
+        };
+        let mut registers = [Register::A, Register::B, Register::C].map(Expr::Register);
+        let mut outputs = Vec::new();
+        for (idx, instruction) in body.iter().enumerate() {
+            let [a, b, c] = registers.clone();
+            match *instruction {
+                Instruction::Adv(combo) => {
+                    registers[0] = Expr::shr(a, Expr::combo(combo, &registers))
+                }
+                Instruction::Bxl(literal) => {
+                    registers[1] = Expr::xor(b, Expr::Literal(literal.into()))
+                }
+                Instruction::Bst(combo) => {
+                    registers[1] = Expr::mod8(Expr::combo(combo, &registers))
+                }
+                Instruction::Jnz(_) => bail!("the program has another jump at {}", idx * 2),
+                Instruction::Bxc => registers[1] = Expr::xor(b, c),
+                Instruction::Out(combo) => outputs.push(Expr::mod8(Expr::combo(combo, &registers))),
+                Instruction::Bdv(combo) => {
+                    registers[1] = Expr::shr(a, Expr::combo(combo, &registers))
+                }
+                Instruction::Cdv(combo) => {
+                    registers[2] = Expr::shr(a, Expr::combo(combo, &registers))
+                }
+            }
+        }
+        ensure!(!outputs.is_empty(), "the loop does not output anything");
+        Ok(Loop { outputs, registers })
+    }
+
+    /// Runs one iteration, returns the outputs and the registers after it.
+    pub fn iterate(&self, registers: &[u64; 3]) -> (Vec<u8>, [u64; 3]) {
+        let outputs = self
+            .outputs
+            .iter()
+            .map(|output| output.eval(registers) as u8)
+            .collect();
+        (
+            outputs,
+            self.registers.clone().map(|expr| expr.eval(registers)),
+        )
+    }
+}
+
+impl Display for Loop {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "do {{")?;
+        writeln!(
+            f,
+            "    // A, B and C are the registers at the start of the iteration"
+        )?;
+        for output in &self.outputs {
+            writeln!(f, "    output {output}")?;
+        }
+        for (register, expr) in [Register::A, Register::B, Register::C]
+            .into_iter()
+            .zip(&self.registers)
+        {
+            if *expr != Expr::Register(register) {
+                writeln!(f, "    {register} = {expr}")?;
+            }
+        }
+        writeln!(f, "}} while A != 0")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use crate::{gen::generate, Solver};
+
+    #[test]
+    fn test_decompile() {
+        let program = Solver::try_parse_input(
+            "Register A: 0\nRegister B: 0\nRegister C: 0\n\nProgram: 2,4,1,3,7,5,0,3,4,1,5,5,3,0",
+        )
+        .unwrap();
+        assert_eq!(
+            program.decompile().unwrap().to_string(),
+            "do {
+    // A, B and C are the registers at the start of the iteration
+    output (((A % 8) ^ 3) ^ (A >> ((A % 8) ^ 3))) % 8
+    A = A >> 3
+    B = ((A % 8) ^ 3) ^ (A >> ((A % 8) ^ 3))
+    C = A >> ((A % 8) ^ 3)
+} while A != 0
+"
+        );
+
+        let error = |program: &str| {
+            let input =
+                format!("Register A: 0\nRegister B: 0\nRegister C: 0\n\nProgram: {program}");
+            Solver::try_parse_input(&input)
+                .unwrap()
+                .decompile()
+                .unwrap_err()
+                .to_string()
+        };
+        assert!(error("5,4,3,2").contains("jump to the start"));
+        assert!(error("3,4,5,4,3,0").contains("another jump at 0"));
+        assert!(error("0,3,3,0").contains("does not output"));
+        assert!(error("0,7,5,4,3,0").contains("invalid instruction at 0"));
+    }
+
+    #[test]
+    fn test_decompile_matches_execute() {
+        let mut rng = StdRng::seed_from_u64(17);
+        for _ in 0..50 {
+            let size = rng.gen_range(1..=16);
+            let input = generate(&mut rng, size);
+            let program = Solver::try_parse_input(&input).unwrap();
+            let decompiled = program.decompile().unwrap();
+            let mut registers = program.registers;
+            let mut outputs = Vec::new();
+            loop {
+                let (output, next) = decompiled.iterate(&registers);
+                outputs.extend(output);
+                registers = next;
+                if registers[0] == 0 {
+                    break;
+                }
+            }
+            assert_eq!(
+                program.execute(),
+                outputs
+                    .iter()
+                    .map(u8::to_string)
+                    .collect::<Vec<_>>()
+                    .join(",")
+            );
+        }
+    }
+}
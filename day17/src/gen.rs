@@ -0,0 +1,31 @@
+//! Random inputs in the shape of the puzzle input.
+
+use itertools::Itertools;
+use rand::{seq::SliceRandom, Rng};
+
+/// Number of octal digits of register A in the real puzzle input.
+pub const DEFAULT_SIZE: usize = 16;
+
+/// Generates a program in the shape of the puzzle with a random register A of
+/// `size` octal digits.
+///
+/// Like the real inputs the program shifts A by three bits per iteration,
+/// mixes the low bits with B and C and prints one digit, with the constants of
+/// the `bxl` instructions and the order of the instructions in the middle of
+/// the loop chosen at random.
+pub fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+    let size = size.clamp(1, 21) as u32;
+    let a = rng.gen_range(8u64.pow(size - 1)..8u64.pow(size));
+
+    let mut body = vec![[1, rng.gen_range(1..8)], [7, 5]];
+    let mut mix = vec![[1, rng.gen_range(1..8)], [4, rng.gen_range(0..8)], [0, 3]];
+    mix.shuffle(rng);
+    body.append(&mut mix);
+    let program = [[2, 4]]
+        .into_iter()
+        .chain(body)
+        .chain([[5, 5], [3, 0]])
+        .flatten()
+        .join(",");
+    format!("Register A: {a}\nRegister B: 0\nRegister C: 0\n\nProgram: {program}\n")
+}
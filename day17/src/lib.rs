@@ -1,3 +1,11 @@
+pub mod asm;
+pub mod decompile;
+pub mod gen;
+pub mod quine;
+#[cfg(feature = "z3")]
+mod smt;
+pub mod vm;
+
 #[derive(Debug, Clone)]
 pub struct Program {
     registers: [u64; 3],
@@ -25,258 +33,141 @@ fn parse_game(input: &str) -> IResult<&str, Program> {
     ))
 }
 
-const A: usize = 0;
-const B: usize = 1;
-const C: usize = 2;
-
 impl Program {
-    fn execute(&self) -> String {
-        let mut registers = self.registers;
-
-        let mut ip = 0;
-
-        #[inline]
-        fn combo(registers: [u64; 3], val: u8) -> u64 {
-            match val {
-                0 => 0,
-                1 => 1,
-                2 => 2,
-                3 => 3,
-                4 => registers[0],
-                5 => registers[1],
-                6 => registers[2],
-                _ => unreachable!(),
-            }
-        }
-        let mut outputs: Vec<u8> = Vec::new();
+    /// The instructions at the even addresses, fails if any of them is invalid.
+    pub fn instructions(&self) -> eyre::Result<Vec<Instruction>> {
+        self.inst
+            .chunks(2)
+            .enumerate()
+            .map(|(idx, pair)| match *pair {
+                [opcode, operand] => Instruction::decode(opcode, operand)
+                    .wrap_err_with(|| format!("invalid instruction at {}", idx * 2)),
+                _ => Err(eyre!("the instruction at {} has no operand", idx * 2)),
+            })
+            .collect()
+    }
 
-        while let Some(op) = self.inst.get(ip as usize).copied() {
-            match op {
-                // adv
-                0 => {
-                    let val = self.inst[ip + 1];
-                    registers[A] = registers[A] >> combo(registers, val);
-                }
-                // bxl
-                1 => {
-                    let val = self.inst[ip + 1];
-                    registers[B] ^= val as u64;
-                }
-                // bst
-                2 => {
-                    let val = self.inst[ip + 1];
-                    registers[B] = combo(registers, val) % 8;
-                }
-                //jnz
-                3 => {
-                    if registers[0] != 0 {
-                        let val = self.inst[ip + 1];
-                        ip = val as usize;
-                        continue;
-                    }
-                }
-                // bxc
-                4 => {
-                    registers[B] ^= registers[C];
-                }
-                // out
-                5 => {
-                    let val = self.inst[ip + 1];
-                    let out = combo(registers, val) % 8;
-                    outputs.push(out as u8);
-                }
-                // bdv
-                6 => {
-                    let val = self.inst[ip + 1];
-                    registers[B] = registers[A] >> combo(registers, val);
-                }
-                // cdv
-                7 => {
-                    let val = self.inst[ip + 1];
-                    registers[C] = registers[A] >> combo(registers, val);
-                }
-                _ => unreachable!(),
-            }
-            ip += 2;
-        }
-        outputs.into_iter().join(",")
+    /// Readable assembly of the program, see [`asm::disassemble`].
+    pub fn disassemble(&self) -> String {
+        asm::disassemble(&self.inst)
     }
 
-    fn analyze_program(&self) -> u64 {
-        let mut instructions: Vec<_> = self.inst[..].chunks_exact(2).collect();
-        // some assertions for our program
-        assert!(
-            instructions.iter().filter(|inst| inst[0] == 3).count() == 1,
-            "we only have 1 jump"
-        );
-        assert!(
-            instructions.last().unwrap()[0] == 3 && instructions.last().unwrap()[1] == 0,
-            "jump is in the end and jumps to 0"
-        );
-        assert!(
-            instructions.iter().filter(|inst| inst[0] == 5).count() == 1,
-            "we only have 1 output"
-        );
-        assert!(
-            instructions[instructions.len() - 2][0] == 5,
-            "output is second to last instruction"
-        );
-        let output_register = match instructions[instructions.len() - 2][1] {
-            4 => A,
-            5 => B,
-            6 => C,
-            _ => panic!("output does not actually output a register"),
-        };
-        // pop print and jump instructions
-        instructions.pop();
-        instructions.pop();
+    /// Pseudo-code of a program that is a single loop, see [`Loop::decompile`].
+    pub fn decompile(&self) -> eyre::Result<Loop> {
+        Loop::decompile(&self.instructions()?)
+    }
 
-        let mut current_best_solution = u64::MAX;
-        let config = z3::Config::new();
-        loop {
-            let ctx = z3::Context::new(&config);
-            let solver = z3::Solver::new(&ctx);
+    fn execute(&self) -> String {
+        let mut vm = Vm::new(self);
+        vm.run().expect("failed to run the program");
+        vm.outputs().iter().join(",")
+    }
 
-            // initial register values
-            const REGISTER_LEN: u32 = 64;
-            let reg_a = z3::ast::BV::new_const(&ctx, "reg_a", REGISTER_LEN);
-            let reg_b = z3::ast::BV::from_u64(
-                &ctx,
-                u64::try_from(self.registers[B]).expect("starting values are not larger than u64"),
-                REGISTER_LEN,
-            );
-            let reg_c = z3::ast::BV::from_u64(
-                &ctx,
-                u64::try_from(self.registers[C]).expect("starting values are not larger than u64"),
-                REGISTER_LEN,
-            );
-            let mut registers = [reg_a.clone(), reg_b, reg_c];
-            let bound = z3::ast::BV::from_u64(&ctx, current_best_solution, REGISTER_LEN);
-            solver.assert(&reg_a.bvult(&bound));
+    /// The lowest value of register A that makes the program output itself,
+    /// found with the native [`Backend`].
+    pub fn lowest_quine(&self) -> eyre::Result<u64> {
+        self.lowest_quine_with(Backend::default())
+    }
 
-            fn z3_combo<'a>(
-                ctx: &'a z3::Context,
-                registers: &[z3::ast::BV<'a>; 3],
-                val: u8,
-            ) -> z3::ast::BV<'a> {
-                match val {
-                    0 => z3::ast::BV::from_u64(&ctx, 0, REGISTER_LEN),
-                    1 => z3::ast::BV::from_u64(&ctx, 1, REGISTER_LEN),
-                    2 => z3::ast::BV::from_u64(&ctx, 2, REGISTER_LEN),
-                    3 => z3::ast::BV::from_u64(&ctx, 3, REGISTER_LEN),
-                    4 => registers[0].clone(),
-                    5 => registers[1].clone(),
-                    6 => registers[2].clone(),
-                    _ => unreachable!(),
-                }
+    /// Like [`Program::lowest_quine`], programs in the shape of the puzzle
+    /// inputs are solved with `backend`, all others with the general search of
+    /// [`quine::find_quine`].
+    pub fn lowest_quine_with(&self, backend: Backend) -> eyre::Result<u64> {
+        match (self.single_loop(), backend) {
+            (Err(_), _) => quine::find_quine(self),
+            (Ok(_), Backend::Native) => {
+                quine::solve_loop(&self.decompile()?, self.registers, &self.inst)
             }
-            let and_mask = z3::ast::BV::from_u64(&ctx, 7, REGISTER_LEN);
-
-            for wanted in self.inst.iter() {
-                for instruction in instructions.iter() {
-                    match instruction[0] {
-                        // adv
-                        0 => {
-                            let val = instruction[1];
-                            let shift_amount = z3_combo(&ctx, &registers, val);
-                            let new_val = registers[A].bvlshr(&shift_amount);
-                            registers[A] = new_val;
-                        }
-                        // bxl
-                        1 => {
-                            let val = instruction[1];
-                            let new_val = registers[B].bvxor(&z3::ast::BV::from_u64(
-                                &ctx,
-                                val as u64,
-                                REGISTER_LEN,
-                            ));
-                            registers[B] = new_val;
-                        }
-                        // bst
-                        2 => {
-                            let val = instruction[1];
-                            let val = z3_combo(&ctx, &registers, val);
-                            let new_val = val.bvand(&and_mask);
-                            registers[B] = new_val;
-                        }
-                        // jmp
-                        3 => {
-                            panic!("jump instruction should not be here");
-                        }
-                        // bxc
-                        4 => {
-                            let new_val = registers[B].bvxor(&registers[C]);
-                            registers[B] = new_val;
-                        }
-                        // out
-                        5 => {
-                            panic!("output instruction should not be here");
-                        }
-                        // bdv
-                        6 => {
-                            let val = instruction[1];
-                            let shift_amount = z3_combo(&ctx, &registers, val);
-                            let new_val = registers[A].bvlshr(&shift_amount);
-                            registers[B] = new_val;
-                        }
-                        // cdv
-                        7 => {
-                            let val = instruction[1];
-                            let shift_amount = z3_combo(&ctx, &registers, val);
-                            let new_val = registers[A].bvlshr(&shift_amount);
-                            registers[C] = new_val;
-                        }
-                        _ => unreachable!(),
-                    }
-                }
+            #[cfg(feature = "z3")]
+            (Ok(output_register), Backend::Z3) => smt::lowest_a(self, output_register, &self.inst),
+        }
+    }
 
-                // after instructions, we get an output
-                let wanted = z3::ast::BV::from_u64(&ctx, *wanted as u64, REGISTER_LEN);
-                let actual = registers[output_register].bvand(&and_mask);
-                solver.assert(&wanted._eq(&actual));
+    /// Checks that the program is a loop whose only jump is a `jnz 0` at the
+    /// end, right after its only output of a register, and returns that
+    /// register.
+    fn single_loop(&self) -> eyre::Result<usize> {
+        let instructions = self.instructions()?;
+        let count = |op: fn(&Instruction) -> bool| instructions.iter().filter(|i| op(i)).count();
+        let jumps = count(|i| matches!(i, Instruction::Jnz(_)));
+        ensure!(jumps == 1, "the program has {jumps} jumps instead of one");
+        let outputs = count(|i| matches!(i, Instruction::Out(_)));
+        ensure!(
+            outputs == 1,
+            "the program has {outputs} outputs instead of one"
+        );
+        match instructions.as_slice() {
+            [.., Instruction::Out(Combo::Register(register)), Instruction::Jnz(0)] => {
+                Ok(*register as usize)
             }
-            // we also want the a register to be 0, since this is the end of the program
-            solver.assert(&registers[A]._eq(&z3::ast::BV::from_u64(&ctx, 0, REGISTER_LEN)));
-
-            if solver.check() != z3::SatResult::Sat {
-                break;
+            [.., Instruction::Out(combo), Instruction::Jnz(0)] => {
+                bail!("the program outputs the literal {combo} instead of a register")
             }
-            let model = solver
-                .get_model()
-                .expect("have a model, since we checked for sat");
-            let res = model
-                .get_const_interp(&reg_a)
-                .expect("register A should be in the model");
-            let potential_res = res
-                .as_u64()
-                .expect("register A should be a u64 in the model");
-            current_best_solution = potential_res;
+            [.., Instruction::Jnz(0)] => bail!("the output is not right before the jump"),
+            _ => bail!("the jump is not a jump to the start at the end of the program"),
         }
-        current_best_solution
     }
 }
 
 use core::panic;
 
 use aoc_traits::AdventOfCodeDay;
+use asm::{Combo, Instruction};
+use decompile::Loop;
+use eyre::{bail, ensure, eyre, WrapErr};
 use itertools::Itertools;
 use nom::{bytes::complete::tag, IResult};
-use z3::ast::Ast;
+use parsing::finish;
+use quine::Backend;
+use vm::Vm;
+
+/// Puzzle parameters, only how part 2 is solved.
+#[derive(Debug, Clone, Default)]
+pub struct Params {
+    /// Solver for programs in the shape of the puzzle inputs.
+    pub backend: Backend,
+}
+
+impl Params {
+    /// Overrides a single parameter by name.
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), eyre::Report> {
+        match key {
+            "backend" => self.backend = value.parse()?,
+            _ => eyre::bail!("unknown parameter {key}, expected backend"),
+        }
+        Ok(())
+    }
+}
+
 pub struct Solver;
+impl Solver {
+    pub fn try_parse_input(input: &str) -> Result<Program, eyre::Report> {
+        finish(input, parse_game(input))
+    }
+    pub fn solve_part1_with(input: &Program, _params: &Params) -> String {
+        input.execute()
+    }
+    pub fn solve_part2_with(input: &Program, params: &Params) -> u64 {
+        input
+            .lowest_quine_with(params.backend)
+            .unwrap_or_else(|e| panic!("cannot solve part 2: {e:#}"))
+    }
+}
+
 impl AdventOfCodeDay for Solver {
     type ParsedInput<'a> = Program;
     type Part1Output = String;
     type Part2Output = u64;
 
     fn parse_input(input: &str) -> Self::ParsedInput<'_> {
-        parse_game(input).unwrap().1
+        Self::try_parse_input(input).expect("Failed to parse input")
     }
 
     fn solve_part1(input: &Self::ParsedInput<'_>) -> Self::Part1Output {
-        input.execute()
+        Self::solve_part1_with(input, &Params::default())
     }
     fn solve_part2(input: &Self::ParsedInput<'_>) -> Self::Part2Output {
-        input.analyze_program()
+        Self::solve_part2_with(input, &Params::default())
     }
 }
 
@@ -284,7 +175,7 @@ impl AdventOfCodeDay for Solver {
 mod tests {
     use aoc_traits::AdventOfCodeDay;
 
-    use crate::Solver;
+    use crate::{quine::Backend, Params, Solver};
     const TEST_INPUT: &str = "Register A: 729
 Register B: 0
 Register C: 0
@@ -307,4 +198,49 @@ Program: 0,3,5,4,3,0";
         let parsed = Solver::parse_input(TEST_INPUT2);
         assert_eq!(Solver::solve_part2(&parsed), 117440);
     }
+
+    #[test]
+    fn test_params() {
+        let parsed = Solver::parse_input(TEST_INPUT2);
+        let mut params = Params::default();
+        assert_eq!(params.backend, Backend::Native);
+        assert_eq!(parsed.lowest_quine_with(Backend::Native).unwrap(), 117440);
+
+        params.set("backend", "native").unwrap();
+        assert_eq!(Solver::solve_part2_with(&parsed, &params), 117440);
+        #[cfg(not(feature = "z3"))]
+        assert!(params.set("backend", "z3").is_err());
+        assert!(params.set("backend", "cvc5").is_err());
+        assert!(params.set("solver", "native").is_err());
+    }
+
+    #[cfg(feature = "z3")]
+    #[test]
+    fn test_backends_agree() {
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+
+        use crate::{gen::generate, smt, vm::Vm};
+
+        let parsed = Solver::parse_input(TEST_INPUT2);
+        for backend in [Backend::Native, Backend::Z3] {
+            assert_eq!(parsed.lowest_quine_with(backend).unwrap(), 117440);
+        }
+
+        // generated programs rarely output themselves, so compare the lowest A
+        // for the outputs of their own A instead
+        let mut rng = StdRng::seed_from_u64(24);
+        for _ in 0..10 {
+            let size = rng.gen_range(1..=16);
+            let program = Solver::try_parse_input(&generate(&mut rng, size)).unwrap();
+            let mut vm = Vm::new(&program);
+            vm.run().unwrap();
+            let target = vm.outputs().to_vec();
+            let output_register = program.single_loop().unwrap();
+            let native =
+                crate::quine::solve_loop(&program.decompile().unwrap(), program.registers, &target)
+                    .unwrap();
+            let z3 = smt::lowest_a(&program, output_register, &target).unwrap();
+            assert_eq!(native, z3);
+        }
+    }
 }
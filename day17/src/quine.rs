@@ -0,0 +1,233 @@
+//! Finding the value of register A that makes a program output itself.
+//!
+//! The search makes no assumption about the control flow of the program, it
+//! runs every candidate on the [`Vm`]. What it does assume is that the program
+//! consumes A three bits per output like the puzzle inputs do: the outputs of
+//! `8 * a + d` are one value followed by the outputs of `a`. The answer is then
+//! found one octal digit at a time, starting with the most significant one
+//! that produces the last value of the program.
+//!
+//! Programs that are a single loop like the puzzle inputs are solved by a
+//! [`Backend`], by default without running the [`Vm`] at all.
+
+use std::str::FromStr;
+
+use eyre::{bail, eyre};
+use itertools::Itertools;
+
+use crate::{
+    asm::Register,
+    decompile::Loop,
+    vm::{Breakpoint, Stop, Vm},
+    Program,
+};
+
+/// How [`Program::lowest_quine_with`] solves programs that are a single loop.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Backend {
+    /// The octal-digit search of [`solve_loop`].
+    #[default]
+    Native,
+    /// A bitvector encoding of the loop that z3 minimises.
+    #[cfg(feature = "z3")]
+    Z3,
+}
+
+impl FromStr for Backend {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> eyre::Result<Self> {
+        match s {
+            "native" => Ok(Backend::Native),
+            #[cfg(feature = "z3")]
+            "z3" => Ok(Backend::Z3),
+            #[cfg(not(feature = "z3"))]
+            "z3" => bail!("the z3 backend needs the z3 feature"),
+            _ => bail!("unknown backend {s}, expected native or z3"),
+        }
+    }
+}
+
+/// More steps than any candidate of a program of this size needs.
+const MAX_STEPS: usize = 1 << 20;
+
+/// The digits of A are tried from the most significant one, so the first
+/// match is the lowest A. `matches(a, len)` tells whether `a` outputs the
+/// last `len` values of `target`.
+pub(crate) fn search(target: &[u8], matches: &mut impl FnMut(u64, usize) -> bool) -> Option<u64> {
+    fn descend(
+        prefix: u64,
+        len: usize,
+        target: &[u8],
+        matches: &mut impl FnMut(u64, usize) -> bool,
+    ) -> Option<u64> {
+        if len == target.len() {
+            return Some(prefix);
+        }
+        for digit in 0..8 {
+            // a leading zero would give the same A as its parent
+            if prefix == 0 && digit == 0 {
+                continue;
+            }
+            let a = prefix.checked_mul(8)? + digit;
+            if matches(a, len + 1) {
+                if let Some(a) = descend(a, len + 1, target, matches) {
+                    return Some(a);
+                }
+            }
+        }
+        None
+    }
+    descend(0, 0, target, matches)
+}
+
+/// The lowest A for which `program` outputs its own instructions.
+///
+/// A candidate for which the program fails, e.g. because it loops forever or
+/// reaches an invalid instruction, is no quine, and the search goes on.
+pub fn find_quine(program: &Program) -> eyre::Result<u64> {
+    let target = &program.inst;
+    let found = search(target, &mut |a, len| {
+        let mut vm = Vm::new(program);
+        vm.set_register(Register::A, a);
+        vm.add_breakpoint(Breakpoint::Outputs(len + 1));
+        let stop = vm.run_bounded(MAX_STEPS);
+        matches!(stop, Ok(Stop::Halted)) && vm.outputs() == &target[target.len() - len..]
+    });
+    match found {
+        Some(a) => Ok(a),
+        None => bail!(
+            "no value of register A makes the program output itself, \
+             the search only finds it for programs that consume A three bits per output"
+        ),
+    }
+}
+
+/// The lowest A for which a program that is the loop `body` outputs `target`,
+/// with B and C starting at the values in `registers`.
+///
+/// Every candidate iterates the decompiled loop only until it has output one
+/// value more than it has to match, so this is much faster than
+/// [`find_quine`].
+pub fn solve_loop(body: &Loop, registers: [u64; 3], target: &[u8]) -> eyre::Result<u64> {
+    let [_, b, c] = registers;
+    let found = search(target, &mut |a, len| {
+        let mut registers = [a, b, c];
+        let mut outputs = Vec::new();
+        // every iteration outputs something, so this ends
+        while outputs.len() <= len {
+            let (output, next) = body.iterate(&registers);
+            outputs.extend(output);
+            registers = next;
+            if registers[0] == 0 {
+                break;
+            }
+        }
+        outputs == target[target.len() - len..]
+    });
+    found.ok_or_else(|| {
+        eyre!(
+            "no value of register A makes the loop output {}, \
+             the search only finds it for loops that consume A three bits per output",
+            target.iter().join(",")
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::{find_quine, solve_loop};
+    use crate::{asm::Register, gen::generate, vm::Vm, Solver};
+
+    fn program(program: &str) -> crate::Program {
+        let input = format!("Register A: 0\nRegister B: 0\nRegister C: 0\n\nProgram: {program}");
+        Solver::try_parse_input(&input).unwrap()
+    }
+
+    fn outputs(program: &crate::Program, a: u64) -> Vec<u8> {
+        let mut vm = Vm::new(program);
+        vm.set_register(Register::A, a);
+        vm.run().unwrap();
+        vm.outputs().to_vec()
+    }
+
+    #[test]
+    fn test_find_quine() {
+        let sample = program("0,3,5,4,3,0");
+        assert_eq!(find_quine(&sample).unwrap(), 117440);
+
+        // the same loop with a jump in the middle that both paths survive
+        let branching = program("0,3,3,6,3,6,5,4,3,0");
+        let a = find_quine(&branching).unwrap();
+        assert_eq!(outputs(&branching, a), branching.inst);
+        assert!(branching.decompile().is_err());
+
+        // generated programs only output themselves by chance, but the search
+        // must never claim a wrong answer
+        let mut rng = StdRng::seed_from_u64(23);
+        for _ in 0..20 {
+            let size = rng.gen_range(1..=16);
+            let generated = Solver::try_parse_input(&generate(&mut rng, size)).unwrap();
+            if let Ok(a) = find_quine(&generated) {
+                assert_eq!(outputs(&generated, a), generated.inst);
+            }
+        }
+    }
+
+    #[test]
+    fn test_find_quine_errors() {
+        // shifts A by one bit and then by two, reaching the reserved combo
+        // operand 7 in between if A is 1 by then
+        let failing = program("0,1,3,6,0,7,0,2,5,4,3,0");
+        let mut vm = Vm::new(&failing);
+        vm.set_register(Register::A, 1);
+        assert!(vm.run().is_err());
+        let a = find_quine(&failing).unwrap();
+        assert_eq!(outputs(&failing, a), failing.inst);
+
+        // never changes A, so every candidate loops forever
+        let error = find_quine(&program("5,4,3,0")).unwrap_err().to_string();
+        assert!(error.starts_with("no value of register A"), "{error}");
+
+        // outputs B, which is always 0
+        let error = find_quine(&program("0,3,5,5,3,0")).unwrap_err().to_string();
+        assert!(error.starts_with("no value of register A"), "{error}");
+    }
+
+    #[test]
+    fn test_solve_loop() {
+        let sample = program("0,3,5,4,3,0");
+        let body = sample.decompile().unwrap();
+        assert_eq!(
+            solve_loop(&body, sample.registers, &sample.inst).unwrap(),
+            117440
+        );
+
+        // the lowest A for the outputs of a generated program is at most its own
+        let mut rng = StdRng::seed_from_u64(24);
+        for _ in 0..50 {
+            let size = rng.gen_range(1..=16);
+            let generated = Solver::try_parse_input(&generate(&mut rng, size)).unwrap();
+            let target = outputs(&generated, generated.registers[0]);
+            let body = generated.decompile().unwrap();
+            let a = solve_loop(&body, generated.registers, &target).unwrap();
+            assert!(a <= generated.registers[0]);
+            assert_eq!(outputs(&generated, a), target);
+        }
+
+        // shifts A by two bits per output
+        let error = solve_loop(
+            &program("0,2,5,4,3,0").decompile().unwrap(),
+            [0; 3],
+            &[3, 2, 1],
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(
+            error.starts_with("no value of register A makes the loop output 3,2,1"),
+            "{error}"
+        );
+    }
+}
@@ -0,0 +1,152 @@
+//! The z3 backend for programs that are a single loop, enabled by the `z3`
+//! feature. It needs the system libz3.
+//!
+//! The body of the loop is unrolled once per wanted output into bitvector
+//! expressions over the initial value of A, and z3 is asked for ever smaller
+//! solutions until there are none left.
+
+use eyre::ensure;
+use z3::ast::Ast;
+
+use crate::Program;
+
+const A: usize = 0;
+const B: usize = 1;
+const C: usize = 2;
+
+/// The lowest A for which `program` outputs `target`, `output_register` is the
+/// register of the `out` instruction before the jump at the end.
+pub(crate) fn lowest_a(
+    program: &Program,
+    output_register: usize,
+    target: &[u8],
+) -> eyre::Result<u64> {
+    let mut instructions: Vec<_> = program.inst.chunks_exact(2).collect();
+    // pop print and jump instructions
+    instructions.pop();
+    instructions.pop();
+
+    let mut current_best_solution = u64::MAX;
+    let config = z3::Config::new();
+    loop {
+        let ctx = z3::Context::new(&config);
+        let solver = z3::Solver::new(&ctx);
+
+        // initial register values
+        const REGISTER_LEN: u32 = 64;
+        let reg_a = z3::ast::BV::new_const(&ctx, "reg_a", REGISTER_LEN);
+        let reg_b = z3::ast::BV::from_u64(&ctx, program.registers[B], REGISTER_LEN);
+        let reg_c = z3::ast::BV::from_u64(&ctx, program.registers[C], REGISTER_LEN);
+        let mut registers = [reg_a.clone(), reg_b, reg_c];
+        let bound = z3::ast::BV::from_u64(&ctx, current_best_solution, REGISTER_LEN);
+        solver.assert(&reg_a.bvult(&bound));
+
+        fn z3_combo<'a>(
+            ctx: &'a z3::Context,
+            registers: &[z3::ast::BV<'a>; 3],
+            val: u8,
+        ) -> z3::ast::BV<'a> {
+            match val {
+                0 => z3::ast::BV::from_u64(ctx, 0, REGISTER_LEN),
+                1 => z3::ast::BV::from_u64(ctx, 1, REGISTER_LEN),
+                2 => z3::ast::BV::from_u64(ctx, 2, REGISTER_LEN),
+                3 => z3::ast::BV::from_u64(ctx, 3, REGISTER_LEN),
+                4 => registers[0].clone(),
+                5 => registers[1].clone(),
+                6 => registers[2].clone(),
+                _ => unreachable!(),
+            }
+        }
+        let and_mask = z3::ast::BV::from_u64(&ctx, 7, REGISTER_LEN);
+        let zero = z3::ast::BV::from_u64(&ctx, 0, REGISTER_LEN);
+
+        for (idx, wanted) in target.iter().enumerate() {
+            for instruction in instructions.iter() {
+                match instruction[0] {
+                    // adv
+                    0 => {
+                        let val = instruction[1];
+                        let shift_amount = z3_combo(&ctx, &registers, val);
+                        let new_val = registers[A].bvlshr(&shift_amount);
+                        registers[A] = new_val;
+                    }
+                    // bxl
+                    1 => {
+                        let val = instruction[1];
+                        let new_val = registers[B].bvxor(&z3::ast::BV::from_u64(
+                            &ctx,
+                            val as u64,
+                            REGISTER_LEN,
+                        ));
+                        registers[B] = new_val;
+                    }
+                    // bst
+                    2 => {
+                        let val = instruction[1];
+                        let val = z3_combo(&ctx, &registers, val);
+                        let new_val = val.bvand(&and_mask);
+                        registers[B] = new_val;
+                    }
+                    // jmp
+                    3 => {
+                        panic!("jump instruction should not be here");
+                    }
+                    // bxc
+                    4 => {
+                        let new_val = registers[B].bvxor(&registers[C]);
+                        registers[B] = new_val;
+                    }
+                    // out
+                    5 => {
+                        panic!("output instruction should not be here");
+                    }
+                    // bdv
+                    6 => {
+                        let val = instruction[1];
+                        let shift_amount = z3_combo(&ctx, &registers, val);
+                        let new_val = registers[A].bvlshr(&shift_amount);
+                        registers[B] = new_val;
+                    }
+                    // cdv
+                    7 => {
+                        let val = instruction[1];
+                        let shift_amount = z3_combo(&ctx, &registers, val);
+                        let new_val = registers[A].bvlshr(&shift_amount);
+                        registers[C] = new_val;
+                    }
+                    _ => unreachable!(),
+                }
+            }
+
+            // after instructions, we get an output
+            let wanted = z3::ast::BV::from_u64(&ctx, *wanted as u64, REGISTER_LEN);
+            let actual = registers[output_register].bvand(&and_mask);
+            solver.assert(&wanted._eq(&actual));
+            // the jump at the end only repeats the loop while A is not 0
+            if idx + 1 < target.len() {
+                solver.assert(&registers[A]._eq(&zero).not());
+            }
+        }
+        // we also want the a register to be 0, since this is the end of the program
+        solver.assert(&registers[A]._eq(&zero));
+
+        if solver.check() != z3::SatResult::Sat {
+            break;
+        }
+        let model = solver
+            .get_model()
+            .expect("have a model, since we checked for sat");
+        let res = model
+            .get_const_interp(&reg_a)
+            .expect("register A should be in the model");
+        let potential_res = res
+            .as_u64()
+            .expect("register A should be a u64 in the model");
+        current_best_solution = potential_res;
+    }
+    ensure!(
+        current_best_solution != u64::MAX,
+        "no value of register A makes the program output the target"
+    );
+    Ok(current_best_solution)
+}
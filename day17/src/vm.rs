@@ -0,0 +1,270 @@
+//! A step debugger for the 3-bit computer.
+
+use std::io::Write;
+
+use eyre::{bail, eyre, WrapErr};
+use rustc_hash::FxHashMap;
+
+use crate::{
+    asm::{Flow, Instruction, Register},
+    Program,
+};
+
+/// Where [`Vm::run`] stops before the program has halted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Breakpoint {
+    /// Before the instruction at this address is executed.
+    Ip(usize),
+    /// As soon as this many values have been output.
+    Outputs(usize),
+}
+
+/// Why [`Vm::run`] returned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stop {
+    Halted,
+    Breakpoint(Breakpoint),
+}
+
+/// Executes a program one instruction at a time.
+pub struct Vm<'a> {
+    inst: &'a [u8],
+    registers: [u64; 3],
+    ip: usize,
+    outputs: Vec<u8>,
+    steps: usize,
+    breakpoints: Vec<Breakpoint>,
+    trace: Option<Box<dyn Write + 'a>>,
+}
+
+impl<'a> Vm<'a> {
+    /// A machine at the start of `program` with its initial registers.
+    pub fn new(program: &'a Program) -> Self {
+        Vm {
+            inst: &program.inst,
+            registers: program.registers,
+            ip: 0,
+            outputs: Vec::new(),
+            steps: 0,
+            breakpoints: Vec::new(),
+            trace: None,
+        }
+    }
+
+    pub fn registers(&self) -> [u64; 3] {
+        self.registers
+    }
+
+    pub fn register(&self, register: Register) -> u64 {
+        self.registers[register as usize]
+    }
+
+    pub fn set_register(&mut self, register: Register, value: u64) {
+        self.registers[register as usize] = value;
+    }
+
+    pub fn ip(&self) -> usize {
+        self.ip
+    }
+
+    pub fn outputs(&self) -> &[u8] {
+        &self.outputs
+    }
+
+    /// Number of instructions executed so far.
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    pub fn add_breakpoint(&mut self, breakpoint: Breakpoint) {
+        self.breakpoints.push(breakpoint);
+    }
+
+    pub fn clear_breakpoints(&mut self) {
+        self.breakpoints.clear();
+    }
+
+    /// Writes a tab separated line with the step, the address, the opcode, the
+    /// operand and the registers after it for every executed instruction.
+    pub fn trace_to(&mut self, mut writer: impl Write + 'a) -> eyre::Result<()> {
+        writeln!(writer, "step\tip\topcode\toperand\ta\tb\tc")?;
+        self.trace = Some(Box::new(writer));
+        Ok(())
+    }
+
+    /// The instruction at the instruction pointer, `None` if the program has
+    /// halted.
+    pub fn next_instruction(&self) -> Option<eyre::Result<Instruction>> {
+        let &[opcode, operand] = self.inst.get(self.ip..self.ip + 2)? else {
+            unreachable!("a range of two");
+        };
+        Some(
+            Instruction::decode(opcode, operand)
+                .wrap_err_with(|| format!("invalid instruction at {}", self.ip)),
+        )
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.next_instruction().is_none()
+    }
+
+    /// Executes a single instruction, returns `false` if the program had
+    /// already halted.
+    pub fn step(&mut self) -> eyre::Result<bool> {
+        let Some(instruction) = self.next_instruction() else {
+            return Ok(false);
+        };
+        let instruction = instruction?;
+        let ip = self.ip;
+        match instruction.apply(&mut self.registers) {
+            Flow::Next => self.ip += 2,
+            Flow::Jump(target) => self.ip = target,
+            Flow::Output(out) => {
+                self.outputs.push(out);
+                self.ip += 2;
+            }
+        }
+        self.steps += 1;
+        if let Some(trace) = &mut self.trace {
+            let [a, b, c] = self.registers;
+            writeln!(
+                trace,
+                "{}\t{ip}\t{}\t{}\t{a}\t{b}\t{c}",
+                self.steps,
+                self.inst[ip],
+                self.inst[ip + 1]
+            )
+            .wrap_err("failed to write the trace")?;
+        }
+        Ok(true)
+    }
+
+    /// Runs until the program halts or a breakpoint is hit.
+    ///
+    /// At least one instruction is executed, so that calling it again after a
+    /// breakpoint on the instruction pointer continues past it.
+    pub fn run(&mut self) -> eyre::Result<Stop> {
+        self.run_until(|_| Ok(()))
+    }
+
+    /// Like [`Vm::run`], but fails after `max_steps` instructions or as soon as
+    /// the machine is in a state it was in before, which means it loops
+    /// forever.
+    pub fn run_bounded(&mut self, max_steps: usize) -> eyre::Result<Stop> {
+        let start = self.steps;
+        let mut seen = FxHashMap::default();
+        self.run_until(|vm| {
+            if let Some(step) = seen.insert((vm.ip, vm.registers), vm.steps) {
+                bail!(
+                    "infinite loop, the state after step {} repeats the one after step {step}",
+                    vm.steps
+                );
+            }
+            if vm.steps - start >= max_steps && !vm.is_halted() {
+                bail!("did not halt within {max_steps} steps");
+            }
+            Ok(())
+        })
+    }
+
+    fn run_until(
+        &mut self,
+        mut check: impl FnMut(&Self) -> eyre::Result<()>,
+    ) -> eyre::Result<Stop> {
+        let mut first = true;
+        loop {
+            for &breakpoint in &self.breakpoints {
+                let hit = match breakpoint {
+                    Breakpoint::Ip(ip) => !first && self.ip == ip,
+                    Breakpoint::Outputs(count) => !first && self.outputs.len() == count,
+                };
+                if hit {
+                    return Ok(Stop::Breakpoint(breakpoint));
+                }
+            }
+            first = false;
+            if !self.step()? {
+                return Ok(Stop::Halted);
+            }
+            check(self).map_err(|e| eyre!("{e} at {}", self.ip))?;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Breakpoint, Stop, Vm};
+    use crate::{asm::Register, Solver};
+
+    fn program(a: u64, program: &str) -> crate::Program {
+        let input = format!("Register A: {a}\nRegister B: 0\nRegister C: 0\n\nProgram: {program}");
+        Solver::try_parse_input(&input).unwrap()
+    }
+
+    #[test]
+    fn test_breakpoints() {
+        let program = program(729, "0,1,5,4,3,0");
+        let mut vm = Vm::new(&program);
+        vm.add_breakpoint(Breakpoint::Outputs(3));
+        vm.add_breakpoint(Breakpoint::Ip(4));
+        assert_eq!(vm.run().unwrap(), Stop::Breakpoint(Breakpoint::Ip(4)));
+        assert_eq!(vm.outputs(), [4]);
+        assert_eq!(vm.register(Register::A), 364);
+        assert_eq!(vm.run().unwrap(), Stop::Breakpoint(Breakpoint::Ip(4)));
+        assert_eq!(vm.run().unwrap(), Stop::Breakpoint(Breakpoint::Outputs(3)));
+        assert_eq!(vm.outputs(), [4, 6, 3]);
+        assert_eq!(vm.ip(), 4);
+
+        vm.clear_breakpoints();
+        assert_eq!(vm.run().unwrap(), Stop::Halted);
+        assert_eq!(vm.outputs(), [4, 6, 3, 5, 6, 3, 5, 2, 1, 0]);
+        assert_eq!(vm.steps(), 30);
+        assert!(vm.is_halted());
+        assert!(!vm.step().unwrap());
+    }
+
+    #[test]
+    fn test_run_bounded() {
+        // B is the only thing that changes and it flips between two values
+        let looping = program(1, "1,1,3,0");
+        let error = Vm::new(&looping).run_bounded(100).unwrap_err().to_string();
+        assert!(
+            error.starts_with("infinite loop, the state after step 5 repeats the one after step 1"),
+            "{error}"
+        );
+
+        let mut vm = Vm::new(&looping);
+        vm.set_register(Register::A, 0);
+        assert_eq!(vm.run_bounded(100).unwrap(), Stop::Halted);
+
+        let program = program(729, "0,1,5,4,3,0");
+        let error = Vm::new(&program).run_bounded(10).unwrap_err().to_string();
+        assert!(error.contains("within 10 steps"), "{error}");
+        assert_eq!(Vm::new(&program).run_bounded(30).unwrap(), Stop::Halted);
+
+        let invalid = Solver::try_parse_input(
+            "Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: 0,1,5,7",
+        )
+        .unwrap();
+        let error = Vm::new(&invalid).run().unwrap_err().to_string();
+        assert_eq!(error, "invalid instruction at 2");
+    }
+
+    #[test]
+    fn test_trace() {
+        let program = program(10, "5,0,5,1,5,4");
+        let mut trace = Vec::new();
+        let mut vm = Vm::new(&program);
+        vm.trace_to(&mut trace).unwrap();
+        vm.run().unwrap();
+        drop(vm);
+        assert_eq!(
+            String::from_utf8(trace).unwrap(),
+            "step\tip\topcode\toperand\ta\tb\tc
+1\t0\t5\t0\t10\t0\t0
+2\t2\t5\t1\t10\t0\t0
+3\t4\t5\t4\t10\t0\t0
+"
+        );
+    }
+}
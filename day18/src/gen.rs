@@ -0,0 +1,28 @@
+//! Random inputs in the shape of the puzzle input.
+
+use std::fmt::Write;
+
+use rand::{seq::SliceRandom, Rng};
+
+use crate::Params;
+
+/// Number of falling bytes in the real puzzle input.
+pub const DEFAULT_SIZE: usize = 3450;
+
+/// Generates `size` distinct falling bytes inside the default [`Params`] area.
+///
+/// The start and exit corners are never hit. `size` is capped at the number of
+/// remaining positions.
+pub fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+    let side = Params::default().size;
+    let mut positions: Vec<(usize, usize)> = (0..side)
+        .flat_map(|y| (0..side).map(move |x| (x, y)))
+        .filter(|&pos| pos != (0, 0) && pos != (side - 1, side - 1))
+        .collect();
+    positions.shuffle(rng);
+    let mut out = String::new();
+    for (x, y) in positions.into_iter().take(size) {
+        writeln!(out, "{x},{y}").unwrap();
+    }
+    out
+}
@@ -1,97 +1,166 @@
+pub mod gen;
+pub mod vis;
+
 use std::collections::VecDeque;
 
 use aoc_traits::AdventOfCodeDay;
-use eyre::OptionExt;
+use grid::{Grid, Pos};
+use parsing::{error_at, parse_at};
+
+/// Parses a single `x,y` line of `input`.
+fn parse_coord(input: &str, line: &str) -> Result<Pos, eyre::Report> {
+    let (a, b) = line
+        .split_once(',')
+        .ok_or_else(|| error_at(input, line, "invalid input, expected comma"))?;
+    Ok((parse_at(input, a)?, parse_at(input, b)?))
+}
 
 #[derive(Debug, Clone)]
-pub struct Grid<const N: usize> {
-    grid: [[u8; N]; N],
+pub struct Memory {
+    grid: Grid,
 }
-impl<const N: usize> Grid<N> {
-    fn from_input(s: &str, limit: usize) -> Result<Self, eyre::Report> {
-        let mut grid = [[0; N]; N];
+impl Memory {
+    fn from_input(s: &str, size: usize, limit: usize) -> Result<Self, eyre::Report> {
+        let mut memory = Memory {
+            grid: Grid::new(size, size, 0),
+        };
         for line in s.lines().take(limit) {
-            let (a, b) = line
-                .split_once(",")
-                .ok_or_eyre("invalid input, expected comma")?;
-            let (x, y) = (a.parse::<usize>()?, b.parse::<usize>()?);
-            grid[x][y] = 1;
+            memory.add_line(s, line)?;
         }
-
-        Ok(Grid { grid })
+        Ok(memory)
     }
-    fn add_coord(&mut self, x: usize, y: usize) {
-        self.grid[x][y] = 1;
+    /// Corrupts the coordinate on `line`, a line of `input`, and returns it.
+    fn add_line(&mut self, input: &str, line: &str) -> Result<Pos, eyre::Report> {
+        let pos = parse_coord(input, line)?;
+        let cell = self.grid.get_mut(pos).ok_or_else(|| {
+            error_at(
+                input,
+                line,
+                format!("coordinate {line} outside of the grid"),
+            )
+        })?;
+        *cell = 1;
+        Ok(pos)
     }
-    fn find_best_path(&self) -> usize {
+    /// Number of steps from the top left corner to every reachable tile,
+    /// `usize::MAX` for the others.
+    fn distances(&self) -> Grid<usize> {
         let start = (0usize, 0usize);
-        let mut score = [[std::usize::MAX; N]; N];
+        let mut score = Grid::new(self.grid.width(), self.grid.height(), usize::MAX);
         let mut to_visit = VecDeque::new();
         to_visit.push_back((start, 0));
 
         while let Some((idx, incoming_score)) = to_visit.pop_front() {
-            for offset in [(0, 1), (1, 0), (0, -1), (-1, 0)] {
-                let next_idx = (
-                    idx.0.checked_add_signed(offset.0),
-                    idx.1.checked_add_signed(offset.1),
-                );
-                let next_idx = match next_idx {
-                    (Some(x), Some(y)) => {
-                        if x >= N || y >= N {
-                            continue;
-                        }
-                        (x, y)
-                    }
-                    _ => continue,
-                };
-                if self.grid[next_idx.0][next_idx.1] == 1 {
+            for next_idx in self.grid.neighbours4(idx) {
+                if self.grid[next_idx] == 1 {
                     continue;
                 }
                 let next_score = incoming_score + 1;
-                if score[next_idx.0][next_idx.1] <= next_score {
+                if score[next_idx] <= next_score {
                     continue;
                 }
-                score[next_idx.0][next_idx.1] = next_score;
+                score[next_idx] = next_score;
                 to_visit.push_back((next_idx, next_score));
             }
         }
-        score[N - 1][N - 1]
+        score
+    }
+    fn find_best_path(&self) -> usize {
+        self.distances()[(self.grid.width() - 1, self.grid.height() - 1)]
     }
 }
 
+/// Puzzle parameters that differ between the example and the real input.
+#[derive(Debug, Clone)]
+pub struct Params {
+    /// Width and height of the memory space.
+    pub size: usize,
+    /// Number of bytes that have fallen before part 1 looks for a path.
+    pub bytes: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            size: 71,
+            bytes: 1024,
+        }
+    }
+}
+
+impl Params {
+    /// Overrides a single parameter by name.
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), eyre::Report> {
+        match key {
+            "size" => self.size = positive(key, value)?,
+            "bytes" => self.bytes = value.parse()?,
+            _ => eyre::bail!("unknown parameter {key}, expected size or bytes"),
+        }
+        Ok(())
+    }
+}
+
+/// Parses the size of the memory space, which has at least one tile.
+fn positive(key: &str, value: &str) -> Result<usize, eyre::Report> {
+    let value = value.parse()?;
+    eyre::ensure!(value > 0, "{key} must be at least 1, got {value}");
+    Ok(value)
+}
+
 #[derive(Default)]
 pub struct Solver;
+impl Solver {
+    /// Checks the coordinates, the grid is only built once the size is known.
+    pub fn try_parse_input(input: &str) -> Result<&str, eyre::Report> {
+        for line in input.lines() {
+            parse_coord(input, line)?;
+        }
+        Ok(input)
+    }
+    /// Checks that every coordinate lies inside the memory space of `params`.
+    pub fn check_input(input: &str, params: &Params) -> Result<(), eyre::Report> {
+        Memory::from_input(input, params.size, usize::MAX).map(|_| ())
+    }
+    pub fn solve_part1_with(input: &str, params: &Params) -> usize {
+        let grid = Memory::from_input(input, params.size, params.bytes)
+            .unwrap_or_else(|e| panic!("cannot solve part 1: {e:#}"));
+        grid.find_best_path()
+    }
+    pub fn solve_part2_with(input: &str, params: &Params) -> String {
+        let mut grid = Memory::from_input(input, params.size, params.bytes)
+            .unwrap_or_else(|e| panic!("cannot solve part 2: {e:#}"));
+        for line in input.lines().skip(params.bytes) {
+            grid.add_line(input, line)
+                .unwrap_or_else(|e| panic!("cannot solve part 2: {e:#}"));
+            if grid.find_best_path() == usize::MAX {
+                return line.to_owned();
+            }
+        }
+        "no solution found".into()
+    }
+}
+
 impl AdventOfCodeDay for Solver {
     type ParsedInput<'a> = &'a str;
     type Part1Output = usize;
     type Part2Output = String;
 
     fn parse_input(input: &str) -> Self::ParsedInput<'_> {
-        input
+        Self::try_parse_input(input).expect("Failed to parse input")
     }
 
     fn solve_part1(input: &Self::ParsedInput<'_>) -> Self::Part1Output {
-        let grid = Grid::<71>::from_input(input, 1024).unwrap();
-        grid.find_best_path()
+        Self::solve_part1_with(input, &Params::default())
     }
     fn solve_part2(input: &Self::ParsedInput<'_>) -> Self::Part2Output {
-        let mut grid = Grid::<71>::from_input(input, 1024).unwrap();
-        for line in input.lines().skip(1024) {
-            let (a, b) = line.split_once(",").expect("invalid input, expected comma");
-            let (x, y) = (a.parse::<usize>().unwrap(), b.parse::<usize>().unwrap());
-            grid.add_coord(x, y);
-            if grid.find_best_path() == usize::MAX {
-                return line.to_owned();
-            }
-        }
-        "no solution found".into()
+        Self::solve_part2_with(input, &Params::default())
     }
 }
 
 #[cfg(test)]
 mod tests {
 
-    use crate::Grid;
+    use crate::{Memory, Params, Solver};
     const TEST_INPUT_SMALL: &str = "5,4
 4,2
 4,5
@@ -120,16 +189,14 @@ mod tests {
 
     #[test]
     fn test_stage1() {
-        let grid = Grid::<7>::from_input(TEST_INPUT_SMALL, 12).unwrap();
+        let grid = Memory::from_input(TEST_INPUT_SMALL, 7, 12).unwrap();
         assert_eq!(grid.find_best_path(), 22);
     }
     #[test]
     fn test_stage2() {
-        let mut grid = Grid::<7>::from_input(TEST_INPUT_SMALL, 12).unwrap();
+        let mut grid = Memory::from_input(TEST_INPUT_SMALL, 7, 12).unwrap();
         for line in TEST_INPUT_SMALL.lines().skip(12) {
-            let (a, b) = line.split_once(",").expect("invalid input, expected comma");
-            let (x, y) = (a.parse::<usize>().unwrap(), b.parse::<usize>().unwrap());
-            grid.add_coord(x, y);
+            grid.add_line(TEST_INPUT_SMALL, line).unwrap();
             if grid.find_best_path() == usize::MAX {
                 assert_eq!(line, "6,1");
                 return;
@@ -137,4 +204,35 @@ mod tests {
         }
         panic!("no solution found");
     }
+    #[test]
+    fn test_params() {
+        let params = Params { size: 7, bytes: 12 };
+        assert_eq!(Solver::solve_part1_with(TEST_INPUT_SMALL, &params), 22);
+        assert_eq!(Solver::solve_part2_with(TEST_INPUT_SMALL, &params), "6,1");
+        assert!(Solver::check_input(TEST_INPUT_SMALL, &params).is_ok());
+        let params = Params { size: 6, bytes: 12 };
+        let error = Solver::check_input(TEST_INPUT_SMALL, &params).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 6, column 1: coordinate 6,3 outside of the grid"
+        );
+
+        let mut params = Params::default();
+        let error = params.set("size", "0").unwrap_err();
+        assert_eq!(error.to_string(), "size must be at least 1, got 0");
+        params.set("size", "7").unwrap();
+        assert_eq!(params.size, 7);
+    }
+    #[test]
+    fn test_outside_of_the_grid() {
+        let mut grid = Memory::from_input(TEST_INPUT_SMALL, 7, 12).unwrap();
+        let input = "7,0\n0,1000";
+        for line in input.lines() {
+            let error = grid.add_line(input, line).unwrap_err();
+            assert!(
+                error.to_string().ends_with("outside of the grid"),
+                "{error}"
+            );
+        }
+    }
 }
@@ -0,0 +1,81 @@
+//! Frames of the bytes falling into the memory space.
+
+use grid::Grid;
+
+use crate::{Memory, Params};
+
+/// The memory space with a `#` on every corrupted byte.
+fn picture(memory: &Memory) -> Grid {
+    memory
+        .grid
+        .map(|&corrupted| if corrupted == 1 { b'#' } else { b'.' })
+}
+
+/// The memory space with one of the shortest paths from the top left to the
+/// bottom right corner drawn as `O`, `None` if there is no path.
+fn with_shortest_path(memory: &Memory) -> Option<Grid> {
+    let distances = memory.distances();
+    let start = (0, 0);
+    let mut pos = (memory.grid.width() - 1, memory.grid.height() - 1);
+    if distances[pos] == usize::MAX {
+        return None;
+    }
+    let mut grid = picture(memory);
+    while pos != start {
+        grid[pos] = b'O';
+        let distance = distances[pos];
+        pos = memory
+            .grid
+            .neighbours4(pos)
+            .find(|&next| (next == start && distance == 1) || distances[next] == distance - 1)
+            .expect("every tile on the path has a predecessor");
+    }
+    grid[start] = b'O';
+    Some(grid)
+}
+
+/// The first `params.bytes` bytes falling one per frame, followed by the
+/// shortest path through the memory space drawn as `O`.
+pub fn part1(
+    input: &str,
+    params: &Params,
+    frame: &mut dyn FnMut(&Grid),
+) -> Result<(), eyre::Report> {
+    // checks that the bytes land inside the memory space
+    Memory::from_input(input, params.size, params.bytes)?;
+    let mut memory = Memory::from_input(input, params.size, 0)?;
+    frame(&picture(&memory));
+    for line in input.lines().take(params.bytes) {
+        memory.add_line(input, line)?;
+        frame(&picture(&memory));
+    }
+    frame(&with_shortest_path(&memory).unwrap_or_else(|| picture(&memory)));
+    Ok(())
+}
+
+/// The first `params.bytes` bytes and then one frame per byte with the
+/// shortest path drawn as `O`, until the byte that cuts off the exit is drawn
+/// as `X`.
+pub fn part2(
+    input: &str,
+    params: &Params,
+    frame: &mut dyn FnMut(&Grid),
+) -> Result<(), eyre::Report> {
+    // checks that every byte lands inside the memory space
+    Memory::from_input(input, params.size, usize::MAX)?;
+    let mut memory = Memory::from_input(input, params.size, params.bytes)?;
+    frame(&with_shortest_path(&memory).unwrap_or_else(|| picture(&memory)));
+    for line in input.lines().skip(params.bytes) {
+        let (x, y) = memory.add_line(input, line)?;
+        match with_shortest_path(&memory) {
+            Some(grid) => frame(&grid),
+            None => {
+                let mut grid = picture(&memory);
+                grid[(x, y)] = b'X';
+                frame(&grid);
+                break;
+            }
+        }
+    }
+    Ok(())
+}
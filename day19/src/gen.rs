@@ -0,0 +1,49 @@
+//! Random inputs in the shape of the puzzle input.
+
+use std::fmt::Write;
+
+use itertools::Itertools;
+use rand::{seq::SliceRandom, Rng};
+
+/// Number of designs in the real puzzle input.
+pub const DEFAULT_SIZE: usize = 400;
+
+const COLOURS: &[u8] = b"wubrg";
+
+fn stripes<R: Rng>(rng: &mut R, len: usize) -> String {
+    (0..len)
+        .map(|_| char::from(*COLOURS.choose(rng).unwrap()))
+        .collect()
+}
+
+/// Generates a few hundred towel patterns and `size` designs.
+///
+/// Most designs are built from the patterns, the rest are random stripes that
+/// may or may not be possible.
+pub fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+    let mut patterns = (0..rng.gen_range(300..500))
+        .map(|_| {
+            let len = rng.gen_range(1..=8);
+            stripes(rng, len)
+        })
+        .unique()
+        // without one of the colours on its own some designs become impossible
+        .filter(|pattern| pattern != "g")
+        .collect::<Vec<_>>();
+    patterns.shuffle(rng);
+
+    let mut out = format!("{}\n\n", patterns.join(", "));
+    for _ in 0..size {
+        let design = match rng.gen_bool(0.7) {
+            true => (0..rng.gen_range(4..12))
+                .map(|_| patterns.choose(rng).unwrap().as_str())
+                .collect(),
+            false => {
+                let len = rng.gen_range(20..60);
+                stripes(rng, len)
+            }
+        };
+        writeln!(out, "{design}").unwrap();
+    }
+    out
+}
@@ -1,3 +1,5 @@
+pub mod gen;
+
 #[derive(Debug)]
 pub struct Game<'a> {
     patterns: Vec<&'a str>,
@@ -41,14 +43,21 @@ use std::collections::HashMap;
 
 use aoc_traits::AdventOfCodeDay;
 use nom::{bytes::complete::tag, character::complete::newline, IResult};
+use parsing::finish;
 pub struct Solver;
+impl Solver {
+    pub fn try_parse_input(input: &str) -> Result<Game<'_>, eyre::Report> {
+        finish(input, parse_game(input))
+    }
+}
+
 impl AdventOfCodeDay for Solver {
     type ParsedInput<'a> = Game<'a>;
     type Part1Output = usize;
     type Part2Output = usize;
 
     fn parse_input(input: &str) -> Self::ParsedInput<'_> {
-        parse_game(input).unwrap().1
+        Self::try_parse_input(input).expect("Failed to parse input")
     }
 
     fn solve_part1(input: &Self::ParsedInput<'_>) -> Self::Part1Output {
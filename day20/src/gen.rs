@@ -0,0 +1,40 @@
+//! Random inputs in the shape of the puzzle input.
+
+use std::collections::VecDeque;
+
+use grid::Grid;
+use rand::Rng;
+
+/// Side length of the real puzzle input.
+pub const DEFAULT_SIZE: usize = 141;
+
+/// Generates a `size` by `size` racetrack, a single path from `S` to `E`.
+///
+/// The track is the path between two opposite corners of a maze without
+/// loops, every other floor tile is turned back into a wall.
+pub fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+    let maze = grid::gen::maze(rng, size.max(5), size.max(5), 0.0);
+    let start = (1, maze.height() - 2);
+    let end = (maze.width() - 2, 1);
+
+    let mut previous = Grid::new(maze.width(), maze.height(), None);
+    let mut to_visit = VecDeque::from([start]);
+    while let Some(pos) = to_visit.pop_front() {
+        for next in maze.neighbours4(pos) {
+            if maze[next] == b'.' && next != start && previous[next].is_none() {
+                previous[next] = Some(pos);
+                to_visit.push_back(next);
+            }
+        }
+    }
+
+    let mut track = Grid::new(maze.width(), maze.height(), b'#');
+    let mut pos = end;
+    while let Some(prev) = previous[pos] {
+        track[pos] = b'.';
+        pos = prev;
+    }
+    track[start] = b'S';
+    track[end] = b'E';
+    track.to_string()
+}
@@ -1,98 +1,55 @@
-use std::{collections::VecDeque, str::FromStr, vec};
+pub mod gen;
+pub mod vis;
+
+use std::{collections::VecDeque, str::FromStr};
 
 use aoc_traits::AdventOfCodeDay;
-use rustc_hash::FxHashSet;
+use grid::{Grid, Pos};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 
 #[derive(Debug, Clone)]
-pub struct Grid {
-    x: usize,
-    y: usize,
-    grid: Vec<u8>,
-    start: (usize, usize),
-    end: (usize, usize),
+pub struct Racetrack {
+    grid: Grid,
+    start: Pos,
+    end: Pos,
 }
 
 #[derive(Debug, Clone)]
-pub struct ScoredGrid {
-    score: Vec<usize>,
-    grid: Grid,
+pub struct ScoredRacetrack {
+    score: Grid<usize>,
+    track: Racetrack,
 }
 
-impl FromStr for Grid {
+impl FromStr for Racetrack {
     type Err = eyre::Report;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let x = s
-            .lines()
-            .next()
-            .ok_or_else(|| eyre::eyre!("empty input"))?
-            .len();
-        let mut gy = 0;
-
-        let mut grid = Vec::with_capacity(x * x);
-        let mut start = None;
-        let mut end = None;
-        for (y, line) in s.lines().enumerate() {
-            gy += 1;
-            for (x, c) in line.chars().enumerate() {
-                if c == 'S' {
-                    start = Some((x, y));
-                }
-                if c == 'E' {
-                    end = Some((x, y));
-                }
-                grid.push(c as u8);
-            }
-        }
-
-        Ok(Grid {
-            x,
-            y: gy,
-            grid,
-            start: start.unwrap(),
-            end: end.unwrap(),
-        })
+        let grid: Grid = s.parse()?;
+        let start = grid.marker(b'S')?;
+        let end = grid.marker(b'E')?;
+        Ok(Racetrack { grid, start, end })
     }
 }
 
-impl Grid {
-    fn idx(&self, (x, y): (usize, usize)) -> usize {
-        y * self.x + x
-    }
-    fn get(&self, (x, y): (usize, usize)) -> u8 {
-        self.grid[self.idx((x, y))]
-    }
-
-    fn find_best_path(&self) -> Vec<usize> {
+impl Racetrack {
+    fn find_best_path(&self) -> Grid<usize> {
         let start = self.end;
-        let mut score = vec![std::usize::MAX; self.grid.len()];
+        let mut score = Grid::new(self.grid.width(), self.grid.height(), usize::MAX);
         let mut to_visit = VecDeque::new();
         to_visit.push_back((start, 0));
-        score[self.idx(start)] = 0;
+        score[start] = 0;
 
         while let Some((idx, incoming_score)) = to_visit.pop_front() {
-            for offset in [(0, 1), (1, 0), (0, -1), (-1, 0)] {
-                let next_idx = (
-                    idx.0.checked_add_signed(offset.0),
-                    idx.1.checked_add_signed(offset.1),
-                );
-                let next_idx = match next_idx {
-                    (Some(x), Some(y)) => {
-                        if x >= self.x || y >= self.y {
-                            continue;
-                        }
-                        (x, y)
-                    }
-                    _ => continue,
-                };
-                if self.get(next_idx) == b'#' {
+            for next_idx in self.grid.neighbours4(idx) {
+                if self.grid[next_idx] == b'#' {
                     continue;
                 }
                 let next_score = incoming_score + 1;
-                if score[self.idx(next_idx)] <= next_score {
+                if score[next_idx] <= next_score {
                     continue;
                 }
-                score[self.idx(next_idx)] = next_score;
+                score[next_idx] = next_score;
                 to_visit.push_back((next_idx, next_score));
             }
         }
@@ -100,14 +57,14 @@ impl Grid {
     }
 }
 
-impl ScoredGrid {
+impl ScoredRacetrack {
     fn find_possible_cheats(
         &self,
         limit: usize,
         time_limit: usize,
-        start: (usize, usize),
+        start: Pos,
         current_score: usize,
-        cheating_paths: &mut FxHashSet<((usize, usize), (usize, usize))>,
+        mut found: impl FnMut(Pos),
     ) {
         // find all possible targets in the region allowed by limit
         for y_offset in -(time_limit as isize)..=(time_limit as isize) {
@@ -116,92 +73,128 @@ impl ScoredGrid {
                 if path_length > time_limit || path_length == 0 {
                     continue;
                 }
-                let target = (
-                    start.0.checked_add_signed(x_offset),
-                    start.1.checked_add_signed(y_offset),
-                );
-                let target = match target {
-                    (Some(x), Some(y)) => {
-                        if x >= self.grid.x || y >= self.grid.y {
-                            continue;
-                        }
-                        (x, y)
-                    }
-                    _ => continue,
+                let Some(target) = self.score.offset(start, (x_offset, y_offset)) else {
+                    continue;
                 };
                 if current_score < limit {
                     continue;
                 }
-                let target_score = self.score[self.grid.idx(target)];
+                let target_score = self.score[target];
                 if current_score - limit >= target_score.saturating_add(path_length) {
-                    cheating_paths.insert((start, target));
+                    found(target);
                 }
             }
         }
     }
 
-    fn get_cheating_paths(&self, limit: usize, time_limit: usize) -> usize {
-        let mut current = self.grid.start;
-        let mut current_score = self.score[self.grid.idx(self.grid.start)];
-        let mut cheating_paths = FxHashSet::default();
-        // walk backwards from the start to the end, checking each position for cheating
-        while current != self.grid.end {
+    /// Walks the track from the start towards the end, calling `visit` with
+    /// every position before the end and its distance to the end.
+    fn walk(&self, mut visit: impl FnMut(Pos, usize)) {
+        let mut current = self.track.start;
+        let mut current_score = self.score[self.track.start];
+        while current != self.track.end {
             let mut next_current = current;
-            self.find_possible_cheats(
-                limit,
-                time_limit,
-                current,
-                current_score,
-                &mut cheating_paths,
-            );
-            for offset in [(0, 1), (1, 0), (0, -1), (-1, 0)] {
-                let next_idx = (
-                    current.0.checked_add_signed(offset.0),
-                    current.1.checked_add_signed(offset.1),
-                );
-                let next_idx = match next_idx {
-                    (Some(x), Some(y)) => {
-                        if x >= self.grid.x || y >= self.grid.y {
-                            continue;
-                        }
-                        (x, y)
-                    }
-                    _ => continue,
-                };
-                if self.grid.get(next_idx) == b'#' {
+            visit(current, current_score);
+            for next_idx in self.track.grid.neighbours4(current) {
+                if self.track.grid[next_idx] == b'#' {
                     continue;
                 }
-                if self.score[self.grid.idx(next_idx)] == current_score - 1 {
+                if self.score[next_idx] == current_score - 1 {
                     next_current = next_idx;
                 }
             }
             current = next_current;
             current_score -= 1;
         }
-        cheating_paths.len()
+    }
+
+    fn get_cheating_paths(&self, limit: usize, time_limit: usize) -> usize {
+        let mut track = Vec::new();
+        self.walk(|current, current_score| track.push((current, current_score)));
+        // the track never visits a position twice, so every cheat is only found once
+        #[cfg(feature = "parallel")]
+        let track = track.into_par_iter();
+        #[cfg(not(feature = "parallel"))]
+        let track = track.into_iter();
+        track
+            .map(|(current, current_score)| {
+                let mut cheats = 0;
+                self.find_possible_cheats(limit, time_limit, current, current_score, |_| {
+                    cheats += 1
+                });
+                cheats
+            })
+            .sum()
+    }
+}
+
+/// Puzzle parameters that differ between the example and the real input.
+#[derive(Debug, Clone)]
+pub struct Params {
+    /// Minimum number of picoseconds a cheat has to save.
+    pub threshold: usize,
+    /// Maximum cheat duration in part 1.
+    pub radius1: usize,
+    /// Maximum cheat duration in part 2.
+    pub radius2: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            threshold: 100,
+            radius1: 2,
+            radius2: 20,
+        }
+    }
+}
+
+impl Params {
+    /// Overrides a single parameter by name.
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), eyre::Report> {
+        match key {
+            "threshold" => self.threshold = value.parse()?,
+            "radius1" => self.radius1 = value.parse()?,
+            "radius2" => self.radius2 = value.parse()?,
+            _ => eyre::bail!("unknown parameter {key}, expected threshold, radius1 or radius2"),
+        }
+        Ok(())
     }
 }
 
 #[derive(Default)]
 pub struct Solver;
+impl Solver {
+    pub fn try_parse_input(input: &str) -> Result<ScoredRacetrack, eyre::Report> {
+        let track: Racetrack = input.parse()?;
+        Ok(ScoredRacetrack {
+            score: track.find_best_path(),
+            track,
+        })
+    }
+
+    pub fn solve_part1_with(input: &ScoredRacetrack, params: &Params) -> usize {
+        input.get_cheating_paths(params.threshold, params.radius1)
+    }
+    pub fn solve_part2_with(input: &ScoredRacetrack, params: &Params) -> usize {
+        input.get_cheating_paths(params.threshold, params.radius2)
+    }
+}
+
 impl AdventOfCodeDay for Solver {
-    type ParsedInput<'a> = ScoredGrid;
+    type ParsedInput<'a> = ScoredRacetrack;
     type Part1Output = usize;
     type Part2Output = usize;
 
     fn parse_input(input: &str) -> Self::ParsedInput<'_> {
-        let grid: Grid = input.parse().expect("Failed to parse input");
-        ScoredGrid {
-            score: grid.find_best_path(),
-            grid,
-        }
+        Self::try_parse_input(input).expect("Failed to parse input")
     }
 
     fn solve_part1(input: &Self::ParsedInput<'_>) -> Self::Part1Output {
-        input.get_cheating_paths(100, 2)
+        Self::solve_part1_with(input, &Params::default())
     }
     fn solve_part2(input: &Self::ParsedInput<'_>) -> Self::Part2Output {
-        input.get_cheating_paths(100, 20)
+        Self::solve_part2_with(input, &Params::default())
     }
 }
 
@@ -0,0 +1,33 @@
+//! Frames of the program racing along the track.
+
+use grid::Grid;
+
+use crate::{Params, ScoredRacetrack};
+
+/// One frame per picosecond with the track walked so far as `o`, the program
+/// as `@` and every tile it could cheat its way to from there as `*`.
+fn race(input: &ScoredRacetrack, threshold: usize, radius: usize, frame: &mut dyn FnMut(&Grid)) {
+    let mut grid = input.track.grid.clone();
+    input.walk(|current, current_score| {
+        let mut picture = grid.clone();
+        input.find_possible_cheats(threshold, radius, current, current_score, |target| {
+            picture[target] = b'*'
+        });
+        picture[current] = b'@';
+        frame(&picture);
+        if grid[current] == b'.' {
+            grid[current] = b'o';
+        }
+    });
+    frame(&grid);
+}
+
+/// The race with cheats of up to `params.radius1` picoseconds.
+pub fn part1(input: &ScoredRacetrack, params: &Params, frame: &mut dyn FnMut(&Grid)) {
+    race(input, params.threshold, params.radius1, frame);
+}
+
+/// The race with cheats of up to `params.radius2` picoseconds.
+pub fn part2(input: &ScoredRacetrack, params: &Params, frame: &mut dyn FnMut(&Grid)) {
+    race(input, params.threshold, params.radius2, frame);
+}
@@ -0,0 +1,17 @@
+//! Random inputs in the shape of the puzzle input.
+
+use std::fmt::Write;
+
+use rand::Rng;
+
+/// Number of door codes in the real puzzle input.
+pub const DEFAULT_SIZE: usize = 5;
+
+/// Generates `size` door codes of three digits followed by `A`.
+pub fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+    let mut out = String::new();
+    for _ in 0..size {
+        writeln!(out, "{:03}A", rng.gen_range(1..1000)).unwrap();
+    }
+    out
+}
@@ -1,4 +1,7 @@
+pub mod gen;
+
 use aoc_traits::AdventOfCodeDay;
+use parsing::{error_at, parse_at};
 use rustc_hash::FxHashMap;
 
 struct KeyPad1 {
@@ -291,7 +294,6 @@ impl NestedKeyPads {
 
 fn calculate_score(input: &str, num_type_2_keypads: usize) -> usize {
     let number: usize = input
-        .trim_start_matches("0")
         .trim_end_matches("A")
         .parse()
         .expect("can parse number");
@@ -317,21 +319,79 @@ fn calculate_score(input: &str, num_type_2_keypads: usize) -> usize {
     len * number
 }
 
+/// Puzzle parameters that differ between the parts.
+#[derive(Debug, Clone)]
+pub struct Params {
+    /// Number of robot operated directional keypads in part 1.
+    pub robots1: usize,
+    /// Number of robot operated directional keypads in part 2.
+    pub robots2: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            robots1: 2,
+            robots2: 25,
+        }
+    }
+}
+
+impl Params {
+    /// Overrides a single parameter by name.
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), eyre::Report> {
+        match key {
+            "robots1" => self.robots1 = value.parse()?,
+            "robots2" => self.robots2 = value.parse()?,
+            _ => eyre::bail!("unknown parameter {key}, expected robots1 or robots2"),
+        }
+        Ok(())
+    }
+}
+
 pub struct Solver;
+impl Solver {
+    /// Checks that every line is a door code like `029A`.
+    pub fn try_parse_input(input: &str) -> Result<&str, eyre::Report> {
+        for line in input.lines() {
+            let digits = line
+                .strip_suffix('A')
+                .ok_or_else(|| error_at(input, line, "expected a code ending in A"))?;
+            if let Some(idx) = digits.find(|c: char| !c.is_ascii_digit()) {
+                return Err(error_at(input, &digits[idx..], "expected a digit"));
+            }
+            parse_at::<usize>(input, digits)?;
+        }
+        Ok(input)
+    }
+    pub fn solve_part1_with(input: &str, params: &Params) -> usize {
+        input
+            .lines()
+            .map(|x| calculate_score(x, params.robots1))
+            .sum()
+    }
+    pub fn solve_part2_with(input: &str, params: &Params) -> usize {
+        input
+            .lines()
+            .map(|x| calculate_score(x, params.robots2))
+            .sum()
+    }
+}
+
 impl AdventOfCodeDay for Solver {
     type ParsedInput<'a> = &'a str;
     type Part1Output = usize;
     type Part2Output = usize;
 
     fn parse_input(input: &str) -> Self::ParsedInput<'_> {
-        input
+        Self::try_parse_input(input).expect("Failed to parse input")
     }
 
     fn solve_part1(input: &Self::ParsedInput<'_>) -> Self::Part1Output {
-        input.lines().map(|x| calculate_score(x, 2)).sum()
+        Self::solve_part1_with(input, &Params::default())
     }
     fn solve_part2(input: &Self::ParsedInput<'_>) -> Self::Part2Output {
-        input.lines().map(|x| calculate_score(x, 25)).sum()
+        Self::solve_part2_with(input, &Params::default())
     }
 }
 
@@ -0,0 +1,17 @@
+//! Random inputs in the shape of the puzzle input.
+
+use std::fmt::Write;
+
+use rand::Rng;
+
+/// Number of buyers in the real puzzle input.
+pub const DEFAULT_SIZE: usize = 2000;
+
+/// Generates initial secret numbers for `size` buyers.
+pub fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+    let mut out = String::new();
+    for _ in 0..size {
+        writeln!(out, "{}", rng.gen_range(1..1 << 24)).unwrap();
+    }
+    out
+}
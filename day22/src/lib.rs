@@ -1,4 +1,9 @@
+pub mod gen;
+
 use aoc_traits::AdventOfCodeDay;
+use parsing::parse_at;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 
 #[inline]
 fn mix_and_prune(secret: u64, input: u64) -> u64 {
@@ -62,24 +67,79 @@ fn part2(secret: u64, overall: &mut [u64], n: usize) {
         });
 }
 
+/// Puzzle parameters that differ between the example and the real input.
+#[derive(Debug, Clone)]
+pub struct Params {
+    /// Number of secrets generated per buyer.
+    pub iterations: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params { iterations: 2000 }
+    }
+}
+
+impl Params {
+    /// Overrides a single parameter by name.
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), eyre::Report> {
+        match key {
+            "iterations" => self.iterations = value.parse()?,
+            _ => eyre::bail!("unknown parameter {key}, expected iterations"),
+        }
+        Ok(())
+    }
+}
+
 pub struct Solver;
+impl Solver {
+    pub fn try_parse_input(input: &str) -> Result<Vec<u64>, eyre::Report> {
+        input.lines().map(|x| parse_at(input, x)).collect()
+    }
+    pub fn solve_part1_with(input: &[u64], params: &Params) -> u64 {
+        #[cfg(feature = "parallel")]
+        let secrets = input.par_iter();
+        #[cfg(not(feature = "parallel"))]
+        let secrets = input.iter();
+        secrets.map(|&x| evolve_n(x, params.iterations)).sum()
+    }
+    pub fn solve_part2_with(input: &[u64], params: &Params) -> u64 {
+        let new_overall = || vec![0u64; 20 * 20 * 20 * 20];
+        let add_buyer = |mut overall: Vec<u64>, &x: &u64| {
+            part2(x, &mut overall, params.iterations);
+            overall
+        };
+        // every thread sums up its own share of the buyers
+        #[cfg(feature = "parallel")]
+        let overall =
+            input
+                .par_iter()
+                .fold(new_overall, add_buyer)
+                .reduce(new_overall, |mut a, b| {
+                    a.iter_mut().zip(b).for_each(|(a, b)| *a += b);
+                    a
+                });
+        #[cfg(not(feature = "parallel"))]
+        let overall = input.iter().fold(new_overall(), add_buyer);
+
+        overall.into_iter().max().unwrap()
+    }
+}
+
 impl AdventOfCodeDay for Solver {
     type ParsedInput<'a> = Vec<u64>;
     type Part1Output = u64;
     type Part2Output = u64;
 
     fn parse_input(input: &str) -> Self::ParsedInput<'_> {
-        input.lines().map(|x| x.parse().unwrap()).collect()
+        Self::try_parse_input(input).expect("Failed to parse input")
     }
 
     fn solve_part1(input: &Self::ParsedInput<'_>) -> Self::Part1Output {
-        input.iter().map(|&x| evolve_n(x, 2000)).sum()
+        Self::solve_part1_with(input, &Params::default())
     }
     fn solve_part2(input: &Self::ParsedInput<'_>) -> Self::Part2Output {
-        let mut overall = vec![0u64; 20 * 20 * 20 * 20];
-        input.iter().for_each(|&x| part2(x, &mut overall, 2000));
-
-        overall.into_iter().max().unwrap()
+        Self::solve_part2_with(input, &Params::default())
     }
 }
 
@@ -0,0 +1,51 @@
+//! Random inputs in the shape of the puzzle input.
+
+use std::fmt::Write;
+
+use itertools::Itertools;
+use rand::{seq::SliceRandom, Rng};
+use rustc_hash::FxHashSet;
+
+/// Number of computers in the real puzzle input.
+pub const DEFAULT_SIZE: usize = 520;
+
+/// Generates a LAN of `size` computers, capped at the 676 possible names.
+///
+/// Every computer gets a few random connections, and one group of thirteen
+/// computers is fully connected to form the LAN party.
+pub fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+    let mut names = ('a'..='z')
+        .cartesian_product('a'..='z')
+        .map(|(a, b)| format!("{a}{b}"))
+        .collect::<Vec<_>>();
+    names.shuffle(rng);
+    names.truncate(size);
+
+    let mut edges = FxHashSet::default();
+    let mut connect = |a: usize, b: usize| {
+        if a != b {
+            edges.insert((a.min(b), a.max(b)));
+        }
+    };
+    for a in 0..names.len() {
+        for _ in 0..rng.gen_range(1..=6) {
+            connect(a, rng.gen_range(0..names.len()));
+        }
+    }
+    let party = rand::seq::index::sample(rng, names.len(), names.len().min(13));
+    for (a, b) in party.into_vec().into_iter().tuple_combinations() {
+        connect(a, b);
+    }
+
+    let mut edges = edges.into_iter().collect::<Vec<_>>();
+    edges.shuffle(rng);
+    let mut out = String::new();
+    for (a, b) in edges {
+        let (a, b) = match rng.gen_bool(0.5) {
+            true => (a, b),
+            false => (b, a),
+        };
+        writeln!(out, "{}-{}", names[a], names[b]).unwrap();
+    }
+    out
+}
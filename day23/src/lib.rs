@@ -1,5 +1,8 @@
+pub mod gen;
+
 use aoc_traits::AdventOfCodeDay;
 use itertools::Itertools;
+use parsing::error_at;
 use rustc_hash::{FxHashMap, FxHashSet};
 
 #[derive(Debug)]
@@ -8,14 +11,17 @@ pub struct Graph<'a> {
 }
 
 impl<'a> Graph<'a> {
-    fn parse(input: &'a str) -> Graph<'a> {
+    fn parse(input: &'a str) -> Result<Graph<'a>, eyre::Report> {
         let mut adj: FxHashMap<&'a str, FxHashSet<&'a str>> = FxHashMap::default();
         for line in input.lines() {
-            let (a, b) = line.split_once('-').unwrap();
+            let (a, b) = line
+                .split_once('-')
+                .filter(|(a, b)| !a.is_empty() && !b.is_empty())
+                .ok_or_else(|| error_at(input, line, "expected a connection `a-b`"))?;
             adj.entry(a).or_default().insert(b);
             adj.entry(b).or_default().insert(a);
         }
-        Graph { adj }
+        Ok(Graph { adj })
     }
 }
 
@@ -43,13 +49,19 @@ fn bron_kerbosch<'a>(
 }
 
 pub struct Solver;
+impl Solver {
+    pub fn try_parse_input(input: &str) -> Result<Graph<'_>, eyre::Report> {
+        Graph::parse(input)
+    }
+}
+
 impl AdventOfCodeDay for Solver {
     type ParsedInput<'a> = Graph<'a>;
     type Part1Output = usize;
     type Part2Output = String;
 
     fn parse_input(input: &str) -> Self::ParsedInput<'_> {
-        Graph::parse(input)
+        Self::try_parse_input(input).expect("Failed to parse input")
     }
 
     fn solve_part1(input: &Self::ParsedInput<'_>) -> Self::Part1Output {
@@ -0,0 +1,153 @@
+//! Random inputs in the shape of the puzzle input.
+
+use std::fmt::Write;
+
+use rand::{seq::SliceRandom, Rng};
+use rustc_hash::FxHashSet;
+
+/// Number of bits of the adder in the real puzzle input.
+pub const DEFAULT_SIZE: usize = 45;
+
+/// Number of swapped output pairs in the real puzzle input.
+pub const DEFAULT_SWAPS: usize = 4;
+
+struct Gate {
+    inputs: (String, String),
+    operation: &'static str,
+    output: String,
+}
+
+/// Generates a ripple-carry adder of `size` bits with [`DEFAULT_SWAPS`]
+/// swapped outputs.
+pub fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+    generate_with_swaps(rng, size, DEFAULT_SWAPS)
+}
+
+/// Generates a ripple-carry adder of `bits` bits with random inputs and the
+/// outputs of `swaps` pairs of gates swapped.
+///
+/// Like in the puzzle each swap stays within the full adder of a single bit,
+/// swapped bits are at least three bits apart and the swaps never create a
+/// cycle.
+/// Neither the lowest nor the highest bit is touched. The number of swaps is
+/// capped at what fits into the adder.
+pub fn generate_with_swaps<R: Rng>(rng: &mut R, bits: usize, swaps: usize) -> String {
+    adder(rng, bits, swaps).0
+}
+
+/// Like [`generate_with_swaps`], but also returns the swapped wires.
+pub(crate) fn adder<R: Rng>(rng: &mut R, bits: usize, swaps: usize) -> (String, Vec<String>) {
+    let bits = bits.clamp(2, 63);
+    let mut used = FxHashSet::default();
+    let mut wire = |rng: &mut R| loop {
+        let name: String = (0..3)
+            .map(|_| char::from(rng.gen_range(b'a'..b'x')))
+            .collect();
+        if used.insert(name.clone()) {
+            return name;
+        }
+    };
+    let gate = |a: &str, operation, b: &str, output: String| Gate {
+        inputs: (a.to_string(), b.to_string()),
+        operation,
+        output,
+    };
+
+    // one full adder per bit, `cells[i]` holds the indices of its gates
+    let mut gates = vec![
+        gate("x00", "XOR", "y00", "z00".to_string()),
+        gate("x00", "AND", "y00", wire(rng)),
+    ];
+    let mut cells = vec![vec![0, 1]];
+    for i in 1..bits {
+        let (x, y) = (format!("x{i:02}"), format!("y{i:02}"));
+        let carry = gates.last().unwrap().output.clone();
+        let (sum, overflow, carried) = (wire(rng), wire(rng), wire(rng));
+        let carry_out = match i == bits - 1 {
+            true => format!("z{bits:02}"),
+            false => wire(rng),
+        };
+        let start = gates.len();
+        gates.extend([
+            gate(&x, "XOR", &y, sum.clone()),
+            gate(&x, "AND", &y, overflow.clone()),
+            gate(&sum, "XOR", &carry, format!("z{i:02}")),
+            gate(&sum, "AND", &carry, carried.clone()),
+            gate(&overflow, "OR", &carried, carry_out),
+        ]);
+        cells.push((start..gates.len()).collect());
+    }
+
+    // spread the swaps over the cells 1..bits - 1 with at least two cells in
+    // between, by picking from fewer cells and adding the gaps afterwards
+    let swaps = swaps.min(bits / 3);
+    let mut swapped =
+        rand::seq::index::sample(rng, bits - 2 - 2 * swaps.saturating_sub(1), swaps).into_vec();
+    swapped.sort();
+    let mut wires = Vec::new();
+    for (idx, cell) in swapped.into_iter().enumerate() {
+        let cell = cell + 1 + 2 * idx;
+        let mut pairs: Vec<(usize, usize)> = (0..5)
+            .flat_map(|a| (a + 1..5).map(move |b| (a, b)))
+            // both inputs of the final OR, swapping them changes nothing
+            .filter(|&pair| pair != (1, 3))
+            .collect();
+        pairs.shuffle(rng);
+        for (a, b) in pairs {
+            let (a, b) = (cells[cell][a], cells[cell][b]);
+            swap_outputs(&mut gates, a, b);
+            if is_acyclic(&gates, bits) {
+                wires.extend([gates[a].output.clone(), gates[b].output.clone()]);
+                break;
+            }
+            swap_outputs(&mut gates, a, b);
+        }
+    }
+
+    let mut out = String::new();
+    for name in ["x", "y"] {
+        for i in 0..bits {
+            writeln!(out, "{name}{i:02}: {}", rng.gen_range(0..2)).unwrap();
+        }
+    }
+    out.push('\n');
+    gates.shuffle(rng);
+    for Gate {
+        inputs: (a, b),
+        operation,
+        output,
+    } in gates
+    {
+        let (a, b) = if rng.gen_bool(0.5) { (a, b) } else { (b, a) };
+        writeln!(out, "{a} {operation} {b} -> {output}").unwrap();
+    }
+    wires.sort();
+    (out, wires)
+}
+
+fn swap_outputs(gates: &mut [Gate], a: usize, b: usize) {
+    let output = std::mem::take(&mut gates[a].output);
+    gates[a].output = std::mem::replace(&mut gates[b].output, output);
+}
+
+/// Whether every gate can be evaluated once the `bits` input bits are known.
+fn is_acyclic(gates: &[Gate], bits: usize) -> bool {
+    let mut known: FxHashSet<String> = (0..bits)
+        .flat_map(|i| [format!("x{i:02}"), format!("y{i:02}")])
+        .collect();
+    let mut remaining: Vec<&Gate> = gates.iter().collect();
+    while !remaining.is_empty() {
+        let before = remaining.len();
+        remaining.retain(|gate| {
+            let ready = known.contains(&gate.inputs.0) && known.contains(&gate.inputs.1);
+            if ready {
+                known.insert(gate.output.clone());
+            }
+            !ready
+        });
+        if remaining.len() == before {
+            return false;
+        }
+    }
+    true
+}
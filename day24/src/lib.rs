@@ -1,85 +1,63 @@
-use core::panic;
-use std::collections::VecDeque;
+pub mod gen;
+pub mod netlist;
+#[cfg(feature = "reference")]
+pub mod reference;
 
 use aoc_traits::AdventOfCodeDay;
+use eyre::{bail, eyre};
 use itertools::Itertools;
-use rustc_hash::{FxHashMap, FxHashSet};
+use netlist::{Lanes, Netlist};
+use parsing::error_at;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use rustc_hash::FxHashSet;
 
 #[derive(Debug, Clone)]
 pub struct Program<'a> {
-    inputs: FxHashMap<&'a str, bool>,
-    gates: Vec<Gate<'a>>,
+    /// The input wires and their values.
+    inputs: Vec<(usize, bool)>,
+    netlist: Netlist<'a>,
 }
 
 impl<'a> Program<'a> {
-    fn parse(input: &'a str) -> Program<'a> {
+    fn parse(input: &'a str) -> Result<Program<'a>, eyre::Report> {
         let (inputs, gates) = input
             .split_once("\n\n")
-            .expect("input has gates and inputs");
+            .ok_or_else(|| eyre!("expected inputs and gates separated by an empty line"))?;
 
-        let inputs: FxHashMap<&'a str, bool> = inputs
+        let inputs: Vec<(&'a str, bool)> = inputs
             .lines()
-            .map(|x| x.split_once(": ").unwrap())
-            .map(|(k, v)| (k, v.parse::<u8>().unwrap() == 1))
-            .collect();
-        let gates = gates.lines().map(|x| Gate::parse(x)).collect();
+            .map(|line| {
+                let (k, v) = line
+                    .split_once(": ")
+                    .ok_or_else(|| error_at(input, line, "expected an input `name: value`"))?;
+                match v {
+                    "0" => Ok((k, false)),
+                    "1" => Ok((k, true)),
+                    _ => Err(error_at(input, v, format!("expected 0 or 1, got {v:?}"))),
+                }
+            })
+            .collect::<Result<_, _>>()?;
+        let gates: Vec<Gate> = gates
+            .lines()
+            .map(|line| Gate::parse(input, line))
+            .collect::<Result<_, _>>()?;
 
-        Program { inputs, gates }
+        let netlist = Netlist::compile(input, inputs.iter().map(|&(wire, _)| wire), &gates)?;
+        let inputs = inputs
+            .into_iter()
+            .map(|(wire, value)| (netlist.id(wire).expect("inputs are wires"), value))
+            .collect();
+        Ok(Program { inputs, netlist })
     }
 
-    fn execute(&self, inputs: &FxHashMap<&'a str, bool>) -> Option<FxHashMap<&'a str, bool>> {
-        let mut state = inputs.clone();
-        let mut gates = VecDeque::from(self.gates.clone());
-
-        let mut no_ops = 0;
-        while let Some(gate) = gates.pop_front() {
-            let input1 = state.get(gate.inputs.0);
-            let input2 = state.get(gate.inputs.1);
-            match (input1, input2) {
-                (Some(&input1), Some(&input2)) => {
-                    let output = match gate.operation {
-                        Operation::And => input1 & input2,
-                        Operation::Or => input1 | input2,
-                        Operation::Xor => input1 ^ input2,
-                    };
-                    state.insert(gate.output, output);
-                    no_ops = 0;
-                }
-                _ => {
-                    gates.push_back(gate);
-                    no_ops += 1;
-                    if no_ops == gates.len() {
-                        // no more operations can be performed
-                        return None;
-                    }
-                }
-            }
-        }
-        Some(state)
-    }
-    fn execute_u64(&self, inputs: (u64, u64)) -> Option<u64> {
-        let mut state = self.inputs.clone();
-        for i in 0..64 {
-            let x = format!("x{i:02}");
-            state
-                .get_mut(x.as_str())
-                .map(|v| *v = (inputs.0 >> i) & 1 == 1);
-            let y = format!("y{i:02}");
-            state
-                .get_mut(y.as_str())
-                .map(|v| *v = (inputs.1 >> i) & 1 == 1);
+    /// The output for the values of the inputs in the puzzle.
+    fn execute(&self) -> u64 {
+        let mut values = vec![0; self.netlist.wires()];
+        for &(wire, value) in &self.inputs {
+            values[wire] = Lanes::from(value);
         }
-
-        let out = self.execute(&state);
-        out.map(|state| {
-            let mut out = 0u64;
-            for i in 0..64 {
-                if let Some(&value) = state.get(format!("z{i:02}").as_str()) {
-                    out |= (value as u64) << i;
-                }
-            }
-            out
-        })
+        self.netlist.evaluate(&mut values);
+        self.netlist.output(&values, 0)
     }
 }
 
@@ -91,272 +69,189 @@ pub struct Gate<'a> {
 }
 
 impl<'a> Gate<'a> {
-    fn parse(input: &'a str) -> Gate<'a> {
-        let (inputs, output) = input.split_once(" -> ").unwrap();
+    /// Parses `line`, reporting errors relative to the whole `input`.
+    fn parse(input: &str, line: &'a str) -> Result<Gate<'a>, eyre::Report> {
+        let (inputs, output) = line
+            .split_once(" -> ")
+            .ok_or_else(|| error_at(input, line, "expected a gate `a OP b -> c`"))?;
         let mut words = inputs.split_ascii_whitespace();
-        let input1 = words.next().unwrap();
-        let operation = match words.next().unwrap() {
+        let (Some(input1), Some(operation), Some(input2), None) =
+            (words.next(), words.next(), words.next(), words.next())
+        else {
+            return Err(error_at(input, line, "expected a gate `a OP b -> c`"));
+        };
+        let operation = match operation {
             "AND" => Operation::And,
             "OR" => Operation::Or,
             "XOR" => Operation::Xor,
-            _ => panic!("Invalid operation"),
+            _ => {
+                return Err(error_at(
+                    input,
+                    operation,
+                    format!("invalid operation {operation:?}"),
+                ))
+            }
         };
-        let input2 = words.next().unwrap();
 
-        Gate {
+        Ok(Gate {
             inputs: (input1, input2),
             output,
             operation,
-        }
+        })
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy)]
 pub enum Operation {
     And,
     Or,
     Xor,
 }
 
-pub struct Solver;
-impl AdventOfCodeDay for Solver {
-    type ParsedInput<'a> = Program<'a>;
-    type Part1Output = u64;
-    type Part2Output = String;
+impl Operation {
+    pub fn apply(self, a: Lanes, b: Lanes) -> Lanes {
+        match self {
+            Operation::And => a & b,
+            Operation::Or => a | b,
+            Operation::Xor => a ^ b,
+        }
+    }
+}
 
-    fn parse_input(input: &str) -> Self::ParsedInput<'_> {
+pub struct Solver;
+impl Solver {
+    pub fn try_parse_input(input: &str) -> Result<Program<'_>, eyre::Report> {
         Program::parse(input)
     }
 
-    fn solve_part1(input: &Self::ParsedInput<'_>) -> Self::Part1Output {
-        let state = input.execute(&input.inputs).expect("can solve");
-        let mut out = 0u64;
-        for i in 0..64 {
-            if let Some(&value) = state.get(format!("z{i:02}").as_str()) {
-                out |= (value as u64) << i;
-            }
+    /// The sorted wires whose swaps turn the circuit into an adder.
+    ///
+    /// Assumes a ripple-carry adder like the puzzle input, where each swap
+    /// stays within the full adder of one bit and swapped bits are at least
+    /// two bits apart. Fails if no combination of the swaps found that way
+    /// adds correctly.
+    pub fn try_solve_part2(input: &Program) -> eyre::Result<String> {
+        let netlist = &input.netlist;
+        let num_bits = netlist.x().len();
+        if num_bits == 0 {
+            // without inputs there is no adder to repair
+            return Ok(String::new());
         }
-        out
-    }
-    fn solve_part2(input: &Self::ParsedInput<'_>) -> Self::Part2Output {
-        let num_bits = input.inputs.keys().filter(|k| k.starts_with('x')).count();
 
-        let mut inputs = input.inputs.clone();
-        for v in inputs.values_mut() {
-            *v = false;
-        }
-        let mut game = input.to_owned();
+        let mut game = netlist.clone();
         let mut good_outputs = FxHashSet::default();
         let mut potential_swaps = vec![Vec::new(); num_bits];
-        for i in 0..num_bits {
-            *inputs.get_mut(format!("x{i:02}").as_str()).unwrap() = true;
-            let output = game.execute(&inputs).expect("can solve");
-            let mut wrong_gates = Vec::new();
-            for (k, v) in output.iter().filter(|(k, _)| k.starts_with('z')) {
-                if *v != (k == &format!("z{:02}", i).as_str()) {
-                    // found potentially wrong output gate
-                    wrong_gates.push(*k);
-                }
-            }
-            // set bit i to 1 for x,y
-            *inputs.get_mut(format!("x{i:02}").as_str()).unwrap() = false;
-            *inputs.get_mut(format!("y{i:02}").as_str()).unwrap() = true;
-            let output = game.execute(&inputs).expect("can solve");
-            let mut wrong_gates = Vec::new();
-            for (k, v) in output.iter().filter(|(k, _)| k.starts_with('z')) {
-                if *v != (k == &format!("z{:02}", i).as_str()) {
-                    // found potentially wrong output gate
-                    wrong_gates.push(*k);
-                }
-            }
-            *inputs.get_mut(format!("x{i:02}").as_str()).unwrap() = true;
-            *inputs.get_mut(format!("y{i:02}").as_str()).unwrap() = true;
-            let output = game.execute(&inputs).expect("can solve");
-            let mut wrong_gates = Vec::new();
-            for (k, v) in output.iter().filter(|(k, _)| k.starts_with('z')) {
-                if *v != (k == &format!("z{:02}", i + 1).as_str()) {
-                    // found potentially wrong output gate
-                    wrong_gates.push(*k);
-                }
-            }
+        for (i, swaps) in potential_swaps.iter_mut().enumerate() {
+            // set bit i of x, of y and of both, in one lane each
+            let bit = 1 << i;
+            let inputs = [(bit, 0), (0, bit), (bit, bit)];
+            let expected = [bit, bit, bit << 1];
+            let output = game.add(&inputs);
+            let wrong_gates: Vec<usize> = netlist
+                .z()
+                .iter()
+                .enumerate()
+                .filter(|&(bit, _)| (output[2] ^ expected[2]) >> bit & 1 == 1)
+                .map(|(_, &wire)| wire)
+                .collect();
             if !wrong_gates.is_empty() {
-                let influenced_gates =
-                    find_gates_influencing(&input.gates, &wrong_gates, &good_outputs);
+                let influenced_gates = find_gates_influencing(netlist, &wrong_gates, &good_outputs);
                 // try all possible gate swaps and check if the output is correct
                 for (&a, &b) in influenced_gates.iter().tuple_combinations() {
-                    for gate in game.gates.iter_mut() {
-                        if gate.output == a {
-                            gate.output = b;
-                        } else if gate.output == b {
-                            gate.output = a;
-                        }
-                    }
-                    let mut wrong_gates = Vec::new();
-                    *inputs.get_mut(format!("x{i:02}").as_str()).unwrap() = false;
-                    *inputs.get_mut(format!("y{i:02}").as_str()).unwrap() = true;
-                    let output = game.execute(&inputs);
-                    if let Some(output) = output {
-                        for (k, v) in output.iter().filter(|(k, _)| k.starts_with('z')) {
-                            if *v != (k == &format!("z{:02}", i).as_str()) {
-                                // found potentially wrong output gate
-                                wrong_gates.push(*k);
-                            }
-                        }
-                    } else {
-                        // fail, swap back
-                        for gate in game.gates.iter_mut() {
-                            if gate.output == a {
-                                gate.output = b;
-                            } else if gate.output == b {
-                                gate.output = a;
-                            }
-                        }
-                        continue;
-                    }
-                    *inputs.get_mut(format!("x{i:02}").as_str()).unwrap() = true;
-                    *inputs.get_mut(format!("y{i:02}").as_str()).unwrap() = false;
-                    let output = game.execute(&inputs);
-                    if let Some(output) = output {
-                        for (k, v) in output.iter().filter(|(k, _)| k.starts_with('z')) {
-                            if *v != (k == &format!("z{:02}", i).as_str()) {
-                                // found potentially wrong output gate
-                                wrong_gates.push(*k);
-                            }
-                        }
-                    } else {
-                        // fail, swap back
-                        for gate in game.gates.iter_mut() {
-                            if gate.output == a {
-                                gate.output = b;
-                            } else if gate.output == b {
-                                gate.output = a;
-                            }
-                        }
-                        continue;
-                    }
-                    *inputs.get_mut(format!("x{i:02}").as_str()).unwrap() = true;
-                    *inputs.get_mut(format!("y{i:02}").as_str()).unwrap() = true;
-                    let output = game.execute(&inputs);
-                    if let Some(output) = output {
-                        for (k, v) in output.iter().filter(|(k, _)| k.starts_with('z')) {
-                            if *v != (k == &format!("z{:02}", i + 1).as_str()) {
-                                // found potentially wrong output gate
-                                wrong_gates.push(*k);
-                            }
-                        }
-                    } else {
-                        // fail, swap back
-                        for gate in game.gates.iter_mut() {
-                            if gate.output == a {
-                                gate.output = b;
-                            } else if gate.output == b {
-                                gate.output = a;
-                            }
-                        }
+                    if game.swap_outputs(a, b).is_err() {
+                        // the swap creates a cycle
                         continue;
                     }
-                    if wrong_gates.is_empty() {
+                    if game.add(&inputs) == expected {
                         // found the correct swap
-                        potential_swaps[i].push((a, b));
-                    }
-                    //  swap back
-                    for gate in game.gates.iter_mut() {
-                        if gate.output == a {
-                            gate.output = b;
-                        } else if gate.output == b {
-                            gate.output = a;
-                        }
+                        swaps.push((a, b));
                     }
+                    game.swap_outputs(a, b)
+                        .expect("swapping back restores the original circuit");
                 }
-            } else {
-                let good = format!("z{:02}", i);
-                let influenced_gates =
-                    find_gates_influencing(&input.gates, &[good.as_str()], &good_outputs);
-                good_outputs.extend(influenced_gates.into_iter());
+            } else if let Some(&z) = netlist.z().get(i) {
+                let influenced_gates = find_gates_influencing(netlist, &[z], &good_outputs);
+                good_outputs.extend(influenced_gates);
             }
-
-            // reset inputs
-            *inputs.get_mut(format!("x{i:02}").as_str()).unwrap() = false;
-            *inputs.get_mut(format!("y{i:02}").as_str()).unwrap() = false;
         }
-        // filter potential swaps a bit
-        let mut swap_groups = Vec::new();
-        for i in 0..num_bits - 1 {
-            if potential_swaps[i].is_empty() {
-                continue;
-            }
-            if potential_swaps[i + 1].is_empty() {
-                swap_groups.push(potential_swaps[i].clone());
+        // a broken bit also breaks the carry into the next one, the swap is
+        // among the candidates of the last bit of such a run
+        let swap_groups: Vec<&[(usize, usize)]> = (0..num_bits)
+            .filter(|&i| !potential_swaps[i].is_empty())
+            .filter(|&i| potential_swaps.get(i + 1).is_none_or(Vec::is_empty))
+            .map(|i| potential_swaps[i].as_slice())
+            .collect();
+        // a fixed seed, so that a false positive can be reproduced
+        let mut rng = StdRng::seed_from_u64(24);
+        let mask = u64::MAX >> (64 - num_bits);
+        // without any groups this is the single combination of no swaps
+        let combinations = swap_groups
+            .iter()
+            .map(|group| group.iter())
+            .multi_cartesian_product();
+        'outer: for swaps in combinations {
+            let mut game = netlist.clone();
+            for &&(a, b) in &swaps {
+                if game.swap_outputs(a, b).is_err() {
+                    continue 'outer;
+                }
             }
-        }
-        assert!(swap_groups.len() == 4);
-        for swap0 in &swap_groups[0] {
-            for swap1 in &swap_groups[1] {
-                for swap2 in &swap_groups[2] {
-                    'outer: for swap3 in &swap_groups[3] {
-                        let mut game = input.to_owned();
-                        for gate in game.gates.iter_mut() {
-                            if gate.output == swap0.0 {
-                                gate.output = swap0.1;
-                            } else if gate.output == swap0.1 {
-                                gate.output = swap0.0;
-                            } else if gate.output == swap1.0 {
-                                gate.output = swap1.1;
-                            } else if gate.output == swap1.1 {
-                                gate.output = swap1.0;
-                            } else if gate.output == swap2.0 {
-                                gate.output = swap2.1;
-                            } else if gate.output == swap2.1 {
-                                gate.output = swap2.0;
-                            } else if gate.output == swap3.0 {
-                                gate.output = swap3.1;
-                            } else if gate.output == swap3.1 {
-                                gate.output = swap3.0;
-                            }
-                        }
-                        // 200 reps should be good enough to weed out false positives
-                        for _ in 0..200 {
-                            let x = rand::random::<u64>() % (1 << num_bits);
-                            let y = rand::random::<u64>() % (1 << num_bits);
-                            let z = x + y;
-
-                            if let Some(output) = game.execute_u64((x, y)) {
-                                if output != z {
-                                    continue 'outer;
-                                }
-                            } else {
-                                continue 'outer;
-                            }
-                        }
-                        return [
-                            swap0.0, swap0.1, swap1.0, swap1.1, swap2.0, swap2.1, swap3.0, swap3.1,
-                        ]
-                        .iter()
-                        .sorted()
-                        .join(",");
-                    }
+            // 256 random additions, 64 at a time, should be good enough to
+            // weed out false positives
+            for _ in 0..4 {
+                let inputs: Vec<(u64, u64)> = (0..64)
+                    .map(|_| (rng.gen::<u64>() & mask, rng.gen::<u64>() & mask))
+                    .collect();
+                let sums = inputs.iter().map(|(x, y)| x + y).collect_vec();
+                if game.add(&inputs) != sums {
+                    continue 'outer;
                 }
             }
+            return Ok(swaps
+                .iter()
+                .flat_map(|&&(a, b)| [a, b])
+                .map(|wire| netlist.name(wire))
+                .sorted()
+                .join(","));
         }
+        bail!(
+            "no combination of the swaps found for {} broken bits turns the circuit into an adder",
+            swap_groups.len()
+        )
+    }
+}
+
+impl AdventOfCodeDay for Solver {
+    type ParsedInput<'a> = Program<'a>;
+    type Part1Output = u64;
+    type Part2Output = String;
 
-        panic!("No solution found");
+    fn parse_input(input: &str) -> Self::ParsedInput<'_> {
+        Self::try_parse_input(input).expect("Failed to parse input")
+    }
+
+    fn solve_part1(input: &Self::ParsedInput<'_>) -> Self::Part1Output {
+        input.execute()
+    }
+    fn solve_part2(input: &Self::ParsedInput<'_>) -> Self::Part2Output {
+        Self::try_solve_part2(input).unwrap_or_else(|e| panic!("cannot solve part 2: {e:#}"))
     }
 }
 
-fn find_gates_influencing<'a, 'b>(
-    gates: &[Gate<'a>],
-    outputs: &[&'b str],
-    good: &FxHashSet<&'a str>,
-) -> FxHashSet<&'a str> {
+/// The wires set by gates that `outputs` depend on, without following the
+/// wires in `good`.
+fn find_gates_influencing(
+    netlist: &Netlist,
+    outputs: &[usize],
+    good: &FxHashSet<usize>,
+) -> FxHashSet<usize> {
     let mut influenced_gates = FxHashSet::default();
     let mut wrong_gates = outputs.to_vec();
     while let Some(wrong) = wrong_gates.pop() {
-        for gate in gates {
-            if gate.output == wrong && !good.contains(gate.output) {
-                influenced_gates.insert(gate.output);
-                wrong_gates.push(gate.inputs.0);
-                wrong_gates.push(gate.inputs.1);
-                break;
+        if let Some((a, b)) = netlist.gate_inputs(wrong) {
+            if !good.contains(&wrong) && influenced_gates.insert(wrong) {
+                wrong_gates.push(a);
+                wrong_gates.push(b);
             }
         }
     }
@@ -366,8 +261,9 @@ fn find_gates_influencing<'a, 'b>(
 #[cfg(test)]
 mod tests {
     use aoc_traits::AdventOfCodeDay;
+    use rand::{rngs::StdRng, SeedableRng};
 
-    use crate::Solver;
+    use crate::{gen, Solver};
 
     const TEST_INPUT: &str = "x00: 1
 x01: 0
@@ -422,4 +318,47 @@ tnw OR pbm -> gnj";
         let parsed = Solver::parse_input(TEST_INPUT);
         assert_eq!(Solver::solve_part1(&parsed), 2024);
     }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = Solver::try_parse_input("x00: 1\ny00: 2\n\nx00 AND y00 -> z00").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 6: expected 0 or 1, got \"2\""
+        );
+        let err = Solver::try_parse_input("x00: 1\n\nx00 NAND y00 -> z00").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 3, column 5: invalid operation \"NAND\""
+        );
+        assert!(Solver::try_parse_input("x00: 1").is_err());
+    }
+
+    #[test]
+    fn test_stage2_without_inputs() {
+        let parsed = Solver::parse_input("abc: 1\n\nabc AND abc -> z00");
+        assert_eq!(Solver::solve_part2(&parsed), "");
+    }
+
+    #[test]
+    fn test_stage2_fewer_swaps() {
+        let mut rng = StdRng::seed_from_u64(25);
+        for bits in 2..12 {
+            let (input, swapped) = gen::adder(&mut rng, bits, 4);
+            assert!(swapped.len() < 8);
+            let parsed = Solver::parse_input(&input);
+            assert_eq!(Solver::solve_part2(&parsed), swapped.join(","));
+        }
+    }
+
+    #[test]
+    fn test_stage2_errors() {
+        // no z wire at all, so nothing adds
+        let parsed = Solver::parse_input("x00: 1\ny00: 1\n\nx00 AND y00 -> abc");
+        let error = Solver::try_solve_part2(&parsed).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "no combination of the swaps found for 0 broken bits turns the circuit into an adder"
+        );
+    }
 }
@@ -0,0 +1,313 @@
+//! The circuit compiled into integer-indexed gates in evaluation order.
+
+use eyre::{bail, eyre};
+use itertools::Itertools;
+use parsing::error_at;
+use rustc_hash::FxHashMap;
+
+use crate::{Gate, Operation};
+
+/// Values of a wire for 64 different inputs at once, one per bit.
+pub type Lanes = u64;
+
+/// A gate with its wires replaced by their ids.
+#[derive(Debug, Clone, Copy)]
+struct Node {
+    inputs: (usize, usize),
+    operation: Operation,
+    output: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct Netlist<'a> {
+    /// Name of every wire, indexed by its id.
+    names: Vec<&'a str>,
+    ids: FxHashMap<&'a str, usize>,
+    /// The gate that sets each wire, `None` for the inputs.
+    drivers: Vec<Option<Node>>,
+    /// Every gate after the gates that set its inputs.
+    sorted: Vec<Node>,
+    /// The `x`, `y` and `z` wires from the lowest to the highest bit.
+    x: Vec<usize>,
+    y: Vec<usize>,
+    z: Vec<usize>,
+}
+
+impl<'a> Netlist<'a> {
+    /// Compiles the `gates` over the `inputs` wires, reporting errors relative
+    /// to the whole `input`.
+    pub fn compile(
+        input: &str,
+        inputs: impl IntoIterator<Item = &'a str>,
+        gates: &[Gate<'a>],
+    ) -> eyre::Result<Netlist<'a>> {
+        let mut netlist = Netlist {
+            names: Vec::new(),
+            ids: FxHashMap::default(),
+            drivers: Vec::new(),
+            sorted: Vec::new(),
+            x: Vec::new(),
+            y: Vec::new(),
+            z: Vec::new(),
+        };
+        for wire in inputs
+            .into_iter()
+            .chain(gates.iter().map(|gate| gate.output))
+        {
+            if netlist.ids.insert(wire, netlist.names.len()).is_some() {
+                return Err(error_at(input, wire, format!("wire {wire} is set twice")));
+            }
+            netlist.names.push(wire);
+        }
+        netlist.drivers = vec![None; netlist.names.len()];
+        for gate in gates {
+            let id = |wire| {
+                netlist
+                    .ids
+                    .get(wire)
+                    .copied()
+                    .ok_or_else(|| error_at(input, wire, format!("wire {wire} is never set")))
+            };
+            let node = Node {
+                inputs: (id(gate.inputs.0)?, id(gate.inputs.1)?),
+                operation: gate.operation,
+                output: netlist.ids[gate.output],
+            };
+            netlist.drivers[node.output] = Some(node);
+        }
+        for (prefix, bus) in [
+            ('x', &mut netlist.x),
+            ('y', &mut netlist.y),
+            ('z', &mut netlist.z),
+        ] {
+            *bus = (0..64)
+                .map_while(|bit| netlist.ids.get(format!("{prefix}{bit:02}").as_str()))
+                .copied()
+                .collect();
+        }
+        for (prefix, bus) in [('x', &netlist.x), ('y', &netlist.y)] {
+            // the sum of two numbers has to fit into a u64
+            if bus.len() > 63 {
+                bail!("the {prefix} input has 64 bits, at most 63 are supported");
+            }
+        }
+        netlist.sort()?;
+        Ok(netlist)
+    }
+
+    /// Orders the gates so that each comes after the gates of its inputs, fails
+    /// with the wires of a cycle if there is no such order.
+    fn sort(&mut self) -> eyre::Result<()> {
+        // number of inputs of each gate that are set by a gate not sorted yet
+        let mut pending = vec![0; self.drivers.len()];
+        let mut users = vec![Vec::new(); self.drivers.len()];
+        for node in self.drivers.iter().flatten() {
+            for input in [node.inputs.0, node.inputs.1] {
+                if self.drivers[input].is_some() {
+                    pending[node.output] += 1;
+                    users[input].push(node.output);
+                }
+            }
+        }
+        let mut ready: Vec<usize> = self
+            .drivers
+            .iter()
+            .flatten()
+            .filter(|node| pending[node.output] == 0)
+            .map(|node| node.output)
+            .collect();
+        self.sorted.clear();
+        while let Some(wire) = ready.pop() {
+            self.sorted.extend(self.drivers[wire]);
+            for &user in &users[wire] {
+                pending[user] -= 1;
+                if pending[user] == 0 {
+                    ready.push(user);
+                }
+            }
+        }
+        match pending.iter().position(|&count| count > 0) {
+            Some(start) => bail!("the circuit has a cycle: {}", self.cycle(start, &pending)),
+            None => Ok(()),
+        }
+    }
+
+    /// Follows unsorted inputs back from `start` until a wire repeats, and
+    /// returns the wires of that cycle in the direction the signal flows.
+    fn cycle(&self, start: usize, pending: &[usize]) -> String {
+        let mut path = vec![start];
+        let mut seen = FxHashMap::from_iter([(start, 0)]);
+        loop {
+            let node = self.drivers[*path.last().unwrap()].expect("only gates are pending");
+            let (a, b) = node.inputs;
+            // a pending gate has an input that is set by a pending gate
+            let next = if pending[a] > 0 { a } else { b };
+            if let Some(&idx) = seen.get(&next) {
+                let cycle = &path[idx..];
+                return cycle
+                    .iter()
+                    .rev()
+                    .chain(cycle.last())
+                    .map(|&wire| self.names[wire])
+                    .join(" -> ");
+            }
+            seen.insert(next, path.len());
+            path.push(next);
+        }
+    }
+
+    /// The id of the wire called `name`.
+    pub fn id(&self, name: &str) -> Option<usize> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, wire: usize) -> &'a str {
+        self.names[wire]
+    }
+
+    /// Number of wires, the ids go from 0 to this.
+    pub fn wires(&self) -> usize {
+        self.names.len()
+    }
+
+    /// The `x` input wires from the lowest to the highest bit.
+    pub fn x(&self) -> &[usize] {
+        &self.x
+    }
+
+    /// The `z` output wires from the lowest to the highest bit.
+    pub fn z(&self) -> &[usize] {
+        &self.z
+    }
+
+    /// The inputs of the gate that sets `wire`, `None` for an input wire.
+    pub fn gate_inputs(&self, wire: usize) -> Option<(usize, usize)> {
+        self.drivers[wire].map(|node| node.inputs)
+    }
+
+    /// Swaps the outputs of the gates that set the wires `a` and `b`.
+    ///
+    /// Fails with the cycle if that creates one, the netlist is unchanged then.
+    pub fn swap_outputs(&mut self, a: usize, b: usize) -> eyre::Result<()> {
+        let (Some(node_a), Some(node_b)) = (self.drivers[a], self.drivers[b]) else {
+            return Err(eyre!(
+                "only wires set by a gate can be swapped, not {} and {}",
+                self.names[a],
+                self.names[b]
+            ));
+        };
+        self.drivers[a] = Some(Node {
+            output: a,
+            ..node_b
+        });
+        self.drivers[b] = Some(Node {
+            output: b,
+            ..node_a
+        });
+        if let Err(e) = self.sort() {
+            self.drivers[a] = Some(node_a);
+            self.drivers[b] = Some(node_b);
+            self.sort().expect("the circuit was sorted before");
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    /// Sets every wire that a gate sets in `values`, which holds the value of
+    /// each wire by id and must have the inputs filled in.
+    pub fn evaluate(&self, values: &mut [Lanes]) {
+        for node in &self.sorted {
+            let (a, b) = node.inputs;
+            values[node.output] = node.operation.apply(values[a], values[b]);
+        }
+    }
+
+    /// The number on the `z` wires in `lane` of the evaluated `values`.
+    pub fn output(&self, values: &[Lanes], lane: usize) -> u64 {
+        self.z
+            .iter()
+            .rev()
+            .fold(0, |acc, &wire| acc << 1 | values[wire] >> lane & 1)
+    }
+
+    /// The output for each `(x, y)` of up to 64 `inputs` with all other input
+    /// wires 0, evaluated at once.
+    pub fn add(&self, inputs: &[(u64, u64)]) -> Vec<u64> {
+        assert!(inputs.len() <= 64, "only 64 inputs fit into the lanes");
+        let mut values = vec![0; self.wires()];
+        for (lane, &(x, y)) in inputs.iter().enumerate() {
+            for (bus, number) in [(&self.x, x), (&self.y, y)] {
+                for (bit, &wire) in bus.iter().enumerate() {
+                    values[wire] |= (number >> bit & 1) << lane;
+                }
+            }
+        }
+        self.evaluate(&mut values);
+        (0..inputs.len())
+            .map(|lane| self.output(&values, lane))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use crate::{gen, Solver};
+
+    #[test]
+    fn test_add() {
+        let mut rng = StdRng::seed_from_u64(25);
+        for bits in [2, 10, 45] {
+            let input = gen::generate_with_swaps(&mut rng, bits, 0);
+            let netlist = Solver::try_parse_input(&input).unwrap().netlist;
+            let inputs: Vec<(u64, u64)> = (0..64)
+                .map(|_| (rng.gen_range(0..1 << bits), rng.gen_range(0..1 << bits)))
+                .collect();
+            let sums: Vec<u64> = inputs.iter().map(|(x, y)| x + y).collect();
+            assert_eq!(netlist.add(&inputs), sums);
+        }
+    }
+
+    #[test]
+    fn test_cycles() {
+        let error = Solver::try_parse_input(
+            "x00: 1\ny00: 0\n\nx00 AND abc -> def\ndef OR y00 -> ghi\nghi XOR x00 -> abc\nabc AND y00 -> z00",
+        )
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "the circuit has a cycle: ghi -> abc -> def -> ghi"
+        );
+
+        let input = "x00: 1\ny00: 1\n\nx00 XOR y00 -> abc\nabc AND x00 -> z00\nx00 OR y00 -> z01";
+        let mut netlist = Solver::try_parse_input(input).unwrap().netlist;
+        let [abc, z00, z01] = ["abc", "z00", "z01"].map(|wire| netlist.id(wire).unwrap());
+        let error = netlist.swap_outputs(abc, z00).unwrap_err();
+        assert_eq!(error.to_string(), "the circuit has a cycle: abc -> abc");
+        assert_eq!(netlist.add(&[(1, 1)]), [0b10]);
+        netlist.swap_outputs(abc, z01).unwrap();
+        assert_eq!(netlist.add(&[(1, 1)]), [0b01]);
+        assert!(netlist
+            .swap_outputs(abc, netlist.id("x00").unwrap())
+            .is_err());
+    }
+
+    #[test]
+    fn test_compile_errors() {
+        let error = Solver::try_parse_input("x00: 1\n\nx00 AND y00 -> z00").unwrap_err();
+        assert_eq!(error.to_string(), "line 3, column 9: wire y00 is never set");
+        let error =
+            Solver::try_parse_input("x00: 1\n\nx00 AND x00 -> z00\nx00 OR x00 -> z00").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 4, column 15: wire z00 is set twice"
+        );
+        let inputs: String = (0..64).map(|bit| format!("y{bit:02}: 1\n")).collect();
+        let error = Solver::try_parse_input(&format!("{inputs}\ny00 AND y01 -> z00")).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "the y input has 64 bits, at most 63 are supported"
+        );
+    }
+}
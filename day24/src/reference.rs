@@ -0,0 +1,231 @@
+//! Slow but obviously correct solutions to check [`crate::Solver`] against.
+
+use std::collections::HashMap;
+
+/// Values of a wire for 64 different inputs at once, one per bit.
+type Lanes = u64;
+
+/// Bit `i` of the lane numbers 0 to 63, for `i` below 6.
+const LOW_BITS: [Lanes; 6] = [
+    0xaaaa_aaaa_aaaa_aaaa,
+    0xcccc_cccc_cccc_cccc,
+    0xf0f0_f0f0_f0f0_f0f0,
+    0xff00_ff00_ff00_ff00,
+    0xffff_0000_ffff_0000,
+    0xffff_ffff_0000_0000,
+];
+
+struct Circuit<'a> {
+    /// Index of every wire name.
+    ids: HashMap<&'a str, usize>,
+    /// Value of each input wire given in the puzzle.
+    inputs: Vec<(usize, bool)>,
+    /// `(input, operation, input)` of the gate that sets each wire.
+    gates: Vec<Option<(usize, &'a str, usize)>>,
+}
+
+fn parse(input: &str) -> Circuit<'_> {
+    let (inputs, gates) = input.split_once("\n\n").unwrap();
+    let mut circuit = Circuit {
+        ids: HashMap::new(),
+        inputs: Vec::new(),
+        gates: Vec::new(),
+    };
+    for line in inputs.lines() {
+        let (wire, value) = line.split_once(": ").unwrap();
+        let wire = circuit.id(wire);
+        circuit.inputs.push((wire, value == "1"));
+    }
+    for line in gates.lines() {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let (a, b, output) = (
+            circuit.id(words[0]),
+            circuit.id(words[2]),
+            circuit.id(words[4]),
+        );
+        circuit.gates[output] = Some((a, words[1], b));
+    }
+    circuit
+}
+
+impl<'a> Circuit<'a> {
+    fn id(&mut self, wire: &'a str) -> usize {
+        let next = self.ids.len();
+        let id = *self.ids.entry(wire).or_insert(next);
+        if id == next {
+            self.gates.push(None);
+        }
+        id
+    }
+
+    /// Value of `wire`, following the gates back to the wires in `known`.
+    ///
+    /// Returns `None` if the wire depends on itself or on a wire without a
+    /// value, which swapping outputs can cause. No path back to the inputs
+    /// passes more than all gates.
+    fn value(&self, wire: usize, known: &mut [Option<Lanes>], depth: usize) -> Option<Lanes> {
+        if let Some(value) = known[wire] {
+            return Some(value);
+        }
+        if depth > self.gates.len() {
+            return None;
+        }
+        let (a, operation, b) = self.gates[wire]?;
+        let (a, b) = (
+            self.value(a, known, depth + 1)?,
+            self.value(b, known, depth + 1)?,
+        );
+        let value = match operation {
+            "AND" => a & b,
+            "OR" => a | b,
+            "XOR" => a ^ b,
+            _ => panic!("invalid operation {operation}"),
+        };
+        known[wire] = Some(value);
+        Some(value)
+    }
+
+    /// The `z` wires from the lowest to the highest bit.
+    fn outputs(&self) -> Vec<usize> {
+        let mut wires: Vec<(&str, usize)> = self
+            .ids
+            .iter()
+            .filter(|(wire, _)| wire.starts_with('z'))
+            .map(|(&wire, &id)| (wire, id))
+            .collect();
+        wires.sort();
+        wires.into_iter().map(|(_, id)| id).collect()
+    }
+}
+
+pub fn solve_part1(input: &str) -> u64 {
+    let circuit = parse(input);
+    let mut known = vec![None; circuit.gates.len()];
+    for &(wire, value) in &circuit.inputs {
+        known[wire] = Some(Lanes::from(value));
+    }
+    circuit.outputs().into_iter().rev().fold(0, |acc, wire| {
+        let value = circuit.value(wire, &mut known, 0);
+        acc << 1 | value.expect("the circuit has a loop") & 1
+    })
+}
+
+/// Whether swapping the outputs of the comma separated `answer` wires in pairs
+/// turns the circuit into an adder.
+///
+/// The swaps in the answer are sorted, so every possible pairing is tried, and
+/// each one is checked against every possible input, 64 inputs at a time.
+/// Only feasible for small adders, the real input has 2^90 of them.
+pub fn check_part2(input: &str, answer: &str) -> bool {
+    let circuit = parse(input);
+    let Some(wires) = answer
+        .split(',')
+        .map(|wire| circuit.ids.get(wire).copied())
+        .collect::<Option<Vec<usize>>>()
+    else {
+        return false;
+    };
+    let bits = circuit.inputs.len() / 2;
+    assert!(bits <= 14, "too many inputs to check all of them");
+    let xy: Vec<_> = (0..bits)
+        .map(|i| {
+            (
+                circuit.ids[format!("x{i:02}").as_str()],
+                circuit.ids[format!("y{i:02}").as_str()],
+            )
+        })
+        .collect();
+    let outputs = circuit.outputs();
+
+    pairings(&wires).into_iter().any(|pairs| {
+        let mut swapped = Circuit {
+            ids: HashMap::new(),
+            inputs: Vec::new(),
+            gates: circuit.gates.clone(),
+        };
+        for (a, b) in pairs {
+            swapped.gates.swap(a, b);
+        }
+        // input number `n` adds its lower `bits` bits to its upper `bits` bits,
+        // lane `l` of the pass starting at `first` holds input `first + l`
+        let mut known = vec![None; swapped.gates.len()];
+        (0..1u64 << (2 * bits)).step_by(64).all(|first| {
+            let lane_bits = |bit: usize| match bit {
+                0..6 => LOW_BITS[bit],
+                _ if first >> bit & 1 == 1 => !0,
+                _ => 0,
+            };
+            known.fill(None);
+            for (i, &(x, y)) in xy.iter().enumerate() {
+                known[x] = Some(lane_bits(i));
+                known[y] = Some(lane_bits(bits + i));
+            }
+            let Some(values) = outputs
+                .iter()
+                .map(|&wire| swapped.value(wire, &mut known, 0))
+                .collect::<Option<Vec<Lanes>>>()
+            else {
+                return false;
+            };
+            (0..64).all(|lane| {
+                let n = first + lane;
+                let sum = (n & ((1 << bits) - 1)) + (n >> bits);
+                let z = values
+                    .iter()
+                    .rev()
+                    .fold(0, |acc, value| acc << 1 | value >> lane & 1);
+                z == sum
+            })
+        })
+    })
+}
+
+/// All ways to split `wires` into pairs.
+fn pairings(wires: &[usize]) -> Vec<Vec<(usize, usize)>> {
+    let Some((&first, rest)) = wires.split_first() else {
+        return vec![vec![]];
+    };
+    let mut out = Vec::new();
+    for (idx, &other) in rest.iter().enumerate() {
+        let mut remaining = rest.to_vec();
+        remaining.remove(idx);
+        for mut pairs in pairings(&remaining) {
+            pairs.push((first, other));
+            out.push(pairs);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_traits::AdventOfCodeDay;
+    use itertools::Itertools;
+    use proptest::prelude::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::{gen, Solver};
+
+    proptest! {
+        #[test]
+        fn test_part1_against_reference(seed: u64, bits in 2usize..20, swaps in 0usize..4) {
+            let input = gen::generate_with_swaps(&mut StdRng::seed_from_u64(seed), bits, swaps);
+            let parsed = Solver::parse_input(&input);
+            prop_assert_eq!(Solver::solve_part1(&parsed), super::solve_part1(&input));
+        }
+    }
+
+    proptest! {
+        // every case checks all 2^24 or 2^26 possible inputs
+        #![proptest_config(ProptestConfig::with_cases(4))]
+
+        #[test]
+        fn test_part2_against_reference(seed: u64, bits in 12usize..=13) {
+            let (input, swapped) = gen::adder(&mut StdRng::seed_from_u64(seed), bits, 4);
+            let parsed = Solver::parse_input(&input);
+            let answer = Solver::solve_part2(&parsed);
+            prop_assert!(super::check_part2(&input, &answer), "{answer} does not fix the adder");
+            prop_assert_eq!(answer, swapped.iter().join(","));
+        }
+    }
+}
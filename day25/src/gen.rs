@@ -0,0 +1,35 @@
+//! Random inputs in the shape of the puzzle input.
+
+use rand::Rng;
+
+/// Number of schematics in the real puzzle input.
+pub const DEFAULT_SIZE: usize = 500;
+
+/// Generates `size` lock and key schematics.
+pub fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+    let mut schematics = Vec::with_capacity(size);
+    for _ in 0..size {
+        let lock = rng.gen_bool(0.5);
+        let heights: [usize; 5] = std::array::from_fn(|_| rng.gen_range(0..=5));
+        let rows = (0..7).map(|row| {
+            (0..5)
+                .map(|column| {
+                    // locks fill from the top, keys from the bottom
+                    let filled = match lock {
+                        true => row <= heights[column],
+                        false => 6 - row <= heights[column],
+                    };
+                    if filled {
+                        '#'
+                    } else {
+                        '.'
+                    }
+                })
+                .collect::<String>()
+        });
+        schematics.push(rows.collect::<Vec<_>>().join("\n"));
+    }
+    let mut out = schematics.join("\n\n");
+    out.push('\n');
+    out
+}
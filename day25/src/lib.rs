@@ -1,4 +1,8 @@
+pub mod gen;
+
 use aoc_traits::AdventOfCodeDay;
+use eyre::ensure;
+use parsing::error_at;
 
 #[derive(Debug)]
 pub struct Collection {
@@ -7,13 +11,23 @@ pub struct Collection {
 }
 
 impl Collection {
-    fn parse(input: &str) -> Collection {
+    fn parse(input: &str) -> Result<Collection, eyre::Report> {
         let mut locks = Vec::new();
         let mut keys = Vec::new();
-        for lock_or_key in input.split("\n\n") {
+        for lock_or_key in input.trim_end().split("\n\n") {
+            for line in lock_or_key.lines() {
+                ensure!(
+                    line.len() == 5 && line.bytes().all(|c| c == b'#' || c == b'.'),
+                    error_at(input, line, "expected a row of five `#` or `.`")
+                );
+            }
+            ensure!(
+                lock_or_key.lines().count() == 7,
+                error_at(input, lock_or_key, "expected a schematic of seven rows")
+            );
             let mut lines = lock_or_key.lines();
             let first_line = lines.next().unwrap();
-            if first_line.contains("#") {
+            if first_line == "#####" {
                 // this is a lock
                 let mut val = [0u8; 5];
                 for line in lines {
@@ -25,7 +39,15 @@ impl Collection {
                 }
                 locks.push(val);
             } else {
-                // this is a key
+                // this is a key, its full bottom row keeps every height at 0 or more
+                ensure!(
+                    first_line == "....." && lock_or_key.lines().last() == Some("#####"),
+                    error_at(
+                        input,
+                        lock_or_key,
+                        "expected a lock with a full top row or a key with a full bottom row only"
+                    )
+                );
                 let mut val = [5u8; 5];
                 for line in lines {
                     for (i, c) in line.chars().enumerate() {
@@ -38,18 +60,24 @@ impl Collection {
             }
         }
 
-        Collection { locks, keys }
+        Ok(Collection { locks, keys })
     }
 }
 
 pub struct Solver;
+impl Solver {
+    pub fn try_parse_input(input: &str) -> Result<Collection, eyre::Report> {
+        Collection::parse(input)
+    }
+}
+
 impl AdventOfCodeDay for Solver {
     type ParsedInput<'a> = Collection;
     type Part1Output = usize;
     type Part2Output = &'static str;
 
     fn parse_input(input: &str) -> Self::ParsedInput<'_> {
-        Collection::parse(input)
+        Self::try_parse_input(input).expect("Failed to parse input")
     }
 
     fn solve_part1(input: &Self::ParsedInput<'_>) -> Self::Part1Output {
@@ -121,4 +149,19 @@ mod tests {
         let parsed = Solver::parse_input(TEST_INPUT);
         assert_eq!(Solver::solve_part1(&parsed), 3);
     }
+
+    #[test]
+    fn test_parse_errors() {
+        let error =
+            Solver::try_parse_input(".....\n.....\n.....\n.....\n.....\n.....\n.....").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 1: expected a lock with a full top row or a key with a full bottom row only"
+        );
+        let error =
+            Solver::try_parse_input("####.\n.....\n.....\n.....\n.....\n.....\n#####").unwrap_err();
+        assert!(error.to_string().contains("expected a lock"), "{error}");
+        let error = Solver::try_parse_input("#####\n#####").unwrap_err();
+        assert!(error.to_string().contains("seven rows"), "{error}");
+    }
 }
@@ -0,0 +1,65 @@
+//! Random grids for the input generators of the days.
+
+use rand::{seq::SliceRandom, Rng};
+
+use crate::{Direction, Grid};
+
+/// Carves a maze of `#` walls and `.` floor with a randomised depth first search.
+///
+/// The sides are rounded up to odd numbers of at least 3, so the maze is
+/// surrounded by walls and every floor tile with odd coordinates is connected.
+/// Without `loops` there is exactly one path between two floor tiles, `loops`
+/// is the chance for each remaining inner wall between two corridors to be
+/// removed.
+pub fn maze<R: Rng>(rng: &mut R, width: usize, height: usize, loops: f64) -> Grid {
+    let (width, height) = (width.max(3) | 1, height.max(3) | 1);
+    let mut grid = Grid::new(width, height, b'#');
+    let mut stack = vec![(1, 1)];
+    grid[(1, 1)] = b'.';
+    while let Some(&pos) = stack.last() {
+        let mut dirs = Direction::ALL;
+        dirs.shuffle(rng);
+        let next = dirs.into_iter().find_map(|dir| {
+            let wall = grid.step(pos, dir)?;
+            let next = grid.step(wall, dir)?;
+            (grid[next] == b'#' && next.0 < width - 1 && next.1 < height - 1)
+                .then_some((wall, next))
+        });
+        match next {
+            Some((wall, next)) => {
+                grid[wall] = b'.';
+                grid[next] = b'.';
+                stack.push(next);
+            }
+            None => {
+                stack.pop();
+            }
+        }
+    }
+    for (x, y) in grid.positions().collect::<Vec<_>>() {
+        let inner = x > 0 && y > 0 && x < width - 1 && y < height - 1;
+        // walls between two corridors have exactly one odd coordinate
+        if inner && (x % 2 == 1) != (y % 2 == 1) && rng.gen_bool(loops) {
+            grid[(x, y)] = b'.';
+        }
+    }
+    grid
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::maze;
+
+    #[test]
+    fn test_maze() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let grid = maze(&mut rng, 10, 7, 0.0);
+        assert_eq!((grid.width(), grid.height()), (11, 7));
+        let floor = grid.find_all(&b'.').count();
+        // a spanning tree over the 5x3 cells needs 14 connecting tiles
+        assert_eq!(floor, 15 + 14);
+        assert!(grid.cells()[..11].iter().all(|&c| c == b'#'));
+    }
+}
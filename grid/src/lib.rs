@@ -0,0 +1,402 @@
+//! Two dimensional grids as they appear in most of the puzzles.
+//!
+//! Positions are `(x, y)` pairs with the origin in the top left corner, `x`
+//! growing to the right and `y` growing downwards, matching the row by row
+//! layout of the puzzle text.
+
+use std::{
+    fmt::{self, Display},
+    ops::{Index, IndexMut},
+    str::FromStr,
+};
+
+use eyre::{bail, ensure, eyre};
+
+pub mod gen;
+
+/// A position `(x, y)` inside a grid.
+pub type Pos = (usize, usize);
+
+/// Offsets of the four orthogonal and four diagonal neighbours, clockwise from up.
+pub const OFFSETS8: [(isize, isize); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+/// One of the four orthogonal directions.
+///
+/// The discriminants follow clockwise order starting at [`Direction::Up`], so a
+/// direction can be used to index per-direction arrays via `dir as usize`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[repr(u8)]
+pub enum Direction {
+    Up = 0,
+    Right = 1,
+    Down = 2,
+    Left = 3,
+}
+
+impl Direction {
+    /// All directions in clockwise order.
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// Parses the arrow characters `^`, `>`, `v` and `<`.
+    pub fn from_arrow(c: u8) -> Option<Self> {
+        match c {
+            b'^' => Some(Self::Up),
+            b'>' => Some(Self::Right),
+            b'v' => Some(Self::Down),
+            b'<' => Some(Self::Left),
+            _ => None,
+        }
+    }
+
+    /// The arrow character pointing in this direction, see [`Direction::from_arrow`].
+    pub fn arrow(self) -> u8 {
+        match self {
+            Self::Up => b'^',
+            Self::Right => b'>',
+            Self::Down => b'v',
+            Self::Left => b'<',
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn reverse(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+
+    /// The `(dx, dy)` offset of a single step.
+    pub fn offset(self) -> (isize, isize) {
+        match self {
+            Self::Up => (0, -1),
+            Self::Right => (1, 0),
+            Self::Down => (0, 1),
+            Self::Left => (-1, 0),
+        }
+    }
+
+    /// Moves one step without any bounds checks.
+    ///
+    /// Meant for grids surrounded by walls, where a step can never leave the
+    /// grid. Use [`Grid::step`] if it can.
+    pub fn step(self, (x, y): Pos) -> Pos {
+        let (dx, dy) = self.offset();
+        (x.wrapping_add_signed(dx), y.wrapping_add_signed(dy))
+    }
+}
+
+/// A rectangular grid of cells stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T = u8> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    /// Creates a grid with every cell set to `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Self {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from cells stored row by row.
+    pub fn from_vec(width: usize, height: usize, cells: Vec<T>) -> Result<Self, eyre::Report> {
+        ensure!(
+            cells.len() == width * height,
+            "expected {width}x{height} cells, got {}",
+            cells.len()
+        );
+        Ok(Grid {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn in_bounds(&self, (x, y): Pos) -> bool {
+        x < self.width && y < self.height
+    }
+
+    /// Converts a signed position, e.g. the result of vector arithmetic, into a
+    /// position inside the grid.
+    pub fn checked_pos(&self, (x, y): (isize, isize)) -> Option<Pos> {
+        let pos = (usize::try_from(x).ok()?, usize::try_from(y).ok()?);
+        self.in_bounds(pos).then_some(pos)
+    }
+
+    /// Index of `pos` in the row by row storage, without bounds checks.
+    pub fn idx(&self, (x, y): Pos) -> usize {
+        y * self.width + x
+    }
+
+    /// Inverse of [`Grid::idx`].
+    pub fn pos(&self, idx: usize) -> Pos {
+        (idx % self.width, idx / self.width)
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.in_bounds(pos).then(|| &self.cells[self.idx(pos)])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if !self.in_bounds(pos) {
+            return None;
+        }
+        let idx = self.idx(pos);
+        Some(&mut self.cells[idx])
+    }
+
+    pub fn swap(&mut self, a: Pos, b: Pos) {
+        let (a, b) = (self.idx(a), self.idx(b));
+        self.cells.swap(a, b);
+    }
+
+    /// The position `offset` away from `pos`, if it is inside the grid.
+    pub fn offset(&self, (x, y): Pos, (dx, dy): (isize, isize)) -> Option<Pos> {
+        let pos = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+        self.in_bounds(pos).then_some(pos)
+    }
+
+    /// The neighbour of `pos` in direction `dir`, if it is inside the grid.
+    pub fn step(&self, pos: Pos, dir: Direction) -> Option<Pos> {
+        self.offset(pos, dir.offset())
+    }
+
+    /// The orthogonal neighbours of `pos` inside the grid.
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |dir| self.step(pos, dir))
+    }
+
+    /// The orthogonal and diagonal neighbours of `pos` inside the grid.
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        OFFSETS8
+            .into_iter()
+            .filter_map(move |offset| self.offset(pos, offset))
+    }
+
+    /// All positions, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |idx| (idx % width, idx / width))
+    }
+
+    /// All cells together with their position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T: PartialEq> Grid<T> {
+    /// Positions of all cells equal to `value`, row by row.
+    pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Pos> + 'a {
+        self.iter()
+            .filter(move |(_, cell)| *cell == value)
+            .map(|(pos, _)| pos)
+    }
+}
+
+impl Grid<u8> {
+    /// Position of the single occurrence of `marker`, e.g. `b'S'` for a start tile.
+    pub fn marker(&self, marker: u8) -> Result<Pos, eyre::Report> {
+        let mut found = self.find_all(&marker);
+        let pos = found
+            .next()
+            .ok_or_else(|| eyre!("no {:?} in grid", marker as char))?;
+        if let Some(other) = found.next() {
+            bail!(
+                "{:?} appears more than once, at {pos:?} and {other:?}",
+                marker as char
+            );
+        }
+        Ok(pos)
+    }
+
+    /// Fails unless every cell on the edge of the grid is `wall`.
+    ///
+    /// Solvers that move with [`Direction::step`] call this while parsing, so
+    /// a step from inside the walls can never leave the grid.
+    pub fn ensure_border(&self, wall: u8) -> Result<(), eyre::Report> {
+        let (width, height) = (self.width, self.height);
+        ensure!(width > 0 && height > 0, "empty grid");
+        let border = (0..width)
+            .flat_map(|x| [(x, 0), (x, height - 1)])
+            .chain((0..height).flat_map(|y| [(0, y), (width - 1, y)]));
+        for pos in border {
+            if self[pos] != wall {
+                bail!(
+                    "expected {:?} around the grid, found {:?} at {pos:?}",
+                    wall as char,
+                    self[pos] as char
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Like [`Grid::marker`], but also replaces the marker with `replacement`.
+    pub fn take_marker(&mut self, marker: u8, replacement: u8) -> Result<Pos, eyre::Report> {
+        let pos = self.marker(marker)?;
+        self[pos] = replacement;
+        Ok(pos)
+    }
+}
+
+/// Indexing without bounds checks on the individual coordinates.
+///
+/// An `x` beyond the width silently wraps into the next row, only positions
+/// past the end of the storage panic. Use [`Grid::get`] for checked access.
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &Self::Output {
+        &self.cells[self.idx(pos)]
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut Self::Output {
+        let idx = self.idx(pos);
+        &mut self.cells[idx]
+    }
+}
+
+/// Parses the puzzle text format, one row of single byte cells per line.
+impl FromStr for Grid<u8> {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.trim_end().lines();
+        let width = lines.next().ok_or_else(|| eyre!("empty grid"))?.len();
+        ensure!(width > 0, "empty grid");
+        for (idx, line) in lines.enumerate() {
+            ensure!(
+                line.len() == width,
+                "line {}: expected {width} cells, got {}",
+                idx + 2,
+                line.len()
+            );
+        }
+        let cells: Vec<u8> = s.trim_end().lines().flat_map(|line| line.bytes()).collect();
+        let height = cells.len() / width;
+        Ok(Grid {
+            width,
+            height,
+            cells,
+        })
+    }
+}
+
+impl Display for Grid<u8> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.cells.chunks(self.width) {
+            writeln!(f, "{}", String::from_utf8_lossy(row))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Direction, Grid};
+
+    const TEST_INPUT: &str = "#####
+#S..#
+#.#E#
+#####
+";
+
+    #[test]
+    fn test_parse() {
+        let grid: Grid = TEST_INPUT.parse().unwrap();
+        assert_eq!((grid.width(), grid.height()), (5, 4));
+        assert_eq!(grid[(3, 2)], b'E');
+        assert_eq!(grid.get((5, 0)), None);
+        assert_eq!(grid.marker(b'S').unwrap(), (1, 1));
+        assert!(grid.marker(b'@').is_err());
+        assert!(grid.marker(b'#').is_err());
+        assert_eq!(grid.to_string(), TEST_INPUT);
+        assert!("###\n##\n".parse::<Grid>().is_err());
+        assert!("".parse::<Grid>().is_err());
+        assert!(grid.ensure_border(b'#').is_ok());
+        assert!("###\n#.#\n#..\n"
+            .parse::<Grid>()
+            .unwrap()
+            .ensure_border(b'#')
+            .is_err());
+        assert!("#\n".parse::<Grid>().unwrap().ensure_border(b'#').is_ok());
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid: Grid = TEST_INPUT.parse().unwrap();
+        assert_eq!(
+            grid.neighbours4((0, 0)).collect::<Vec<_>>(),
+            [(1, 0), (0, 1)]
+        );
+        assert_eq!(grid.neighbours4((2, 2)).count(), 4);
+        assert_eq!(grid.neighbours8((4, 3)).count(), 3);
+        assert_eq!(grid.neighbours8((1, 1)).count(), 8);
+        assert_eq!(grid.checked_pos((-1, 0)), None);
+        assert_eq!(grid.checked_pos((4, 3)), Some((4, 3)));
+    }
+
+    #[test]
+    fn test_direction() {
+        for dir in Direction::ALL {
+            assert_eq!(dir.turn_right().turn_left(), dir);
+            assert_eq!(dir.turn_right().turn_right(), dir.reverse());
+            assert_eq!(dir.reverse().step(dir.step((5, 5))), (5, 5));
+            assert_eq!(Direction::from_arrow(dir.arrow()), Some(dir));
+        }
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::from_arrow(b'<'), Some(Direction::Left));
+        assert_eq!(Direction::Left.step((3, 2)), (2, 2));
+    }
+}
@@ -0,0 +1,140 @@
+//! Criterion benchmarks for parsing and both parts of every day.
+//!
+//! Inputs are read from `dayNN/input.txt.age` and decrypted with `AGE_PASSPHRASE`
+//! or the identity file in `AGE_IDENTITY`; days without an input are skipped. Run with
+//!
+//! ```sh
+//! AGE_PASSPHRASE=... cargo bench -p meta --bench solvers -- --save-baseline main
+//! AGE_PASSPHRASE=... cargo bench -p meta --bench solvers -- --baseline main
+//! ```
+//!
+//! to record a baseline and compare a later commit against it. After each run the
+//! median and mean of every benchmark are also written to `bench_output.txt` in
+//! the workspace root as tab-separated values.
+
+use std::{
+    fmt::Display,
+    fmt::Write,
+    path::{Path, PathBuf},
+};
+
+use criterion::{black_box, Criterion};
+use meta::{
+    day::RunnableDay,
+    input::{input_path, read_input, Keys},
+    visit_day, DayVisitor, DEFAULT_YEAR,
+};
+use secrecy::SecretString;
+
+const DAYS: usize = 25;
+const PHASES: [&str; 3] = ["parse", "part1", "part2"];
+
+struct BenchDay<'a> {
+    c: &'a mut Criterion,
+    day: usize,
+    input: &'a str,
+}
+
+impl DayVisitor for BenchDay<'_> {
+    type Output = ();
+
+    fn visit<D>(self)
+    where
+        D: RunnableDay,
+        D::Part1Output: Display,
+        D::Part2Output: Display,
+    {
+        let input = self.input;
+        let parsed = D::parse_input(input);
+        let mut group = self.c.benchmark_group(format!("day{:02}", self.day));
+        group.sample_size(10);
+        group.bench_function("parse", |b| b.iter(|| D::parse_input(black_box(input))));
+        group.bench_function("part1", |b| b.iter(|| D::solve_part1(black_box(&parsed))));
+        group.bench_function("part2", |b| b.iter(|| D::solve_part2(black_box(&parsed))));
+        group.finish();
+    }
+}
+
+fn workspace_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("meta is a workspace member")
+        .to_owned()
+}
+
+fn criterion_dir() -> PathBuf {
+    std::env::var_os("CARGO_TARGET_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| workspace_root().join("target"))
+        .join("criterion")
+}
+
+/// Reads `field.point_estimate` (in nanoseconds) from a criterion `estimates.json`.
+fn point_estimate(estimates: &serde_json::Value, field: &str) -> Option<f64> {
+    estimates.get(field)?.get("point_estimate")?.as_f64()
+}
+
+fn write_summary(days: &[usize]) -> std::io::Result<()> {
+    let mut out = String::from("day\tphase\tmedian_ns\tmean_ns\n");
+    for &day in days {
+        for phase in PHASES {
+            let path = criterion_dir()
+                .join(format!("day{day:02}"))
+                .join(phase)
+                .join("new")
+                .join("estimates.json");
+            let Ok(estimates) = std::fs::read_to_string(&path) else {
+                continue;
+            };
+            let Ok(estimates) = serde_json::from_str::<serde_json::Value>(&estimates) else {
+                continue;
+            };
+            let (Some(median), Some(mean)) = (
+                point_estimate(&estimates, "median"),
+                point_estimate(&estimates, "mean"),
+            ) else {
+                continue;
+            };
+            writeln!(out, "{day}\t{phase}\t{median:.0}\t{mean:.0}").expect("write to string");
+        }
+    }
+    std::fs::write(workspace_root().join("bench_output.txt"), out)
+}
+
+fn main() {
+    let passphrase = std::env::var("AGE_PASSPHRASE").ok().map(SecretString::from);
+    let identities: Vec<PathBuf> = std::env::var_os("AGE_IDENTITY")
+        .into_iter()
+        .map(PathBuf::from)
+        .collect();
+    let keys = Keys::new(passphrase, &identities).expect("failed to load age identities");
+    let mut c = Criterion::default().configure_from_args();
+
+    let root = workspace_root();
+    let mut benched = Vec::new();
+    for day in 1..=DAYS {
+        let path = input_path(&root, DEFAULT_YEAR, day);
+        let input = match read_input(&path, &keys) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("skipping day {day}: {e}");
+                continue;
+            }
+        };
+        visit_day(
+            DEFAULT_YEAR,
+            day,
+            BenchDay {
+                c: &mut c,
+                day,
+                input: &input,
+            },
+        );
+        benched.push(day);
+    }
+
+    c.final_summary();
+    if let Err(e) = write_summary(&benched) {
+        eprintln!("failed to write bench_output.txt: {e}");
+    }
+}
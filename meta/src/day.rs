@@ -0,0 +1,243 @@
+use aoc_traits::AdventOfCodeDay;
+use color_eyre::eyre::{self, bail};
+use grid::Grid;
+use rand::Rng;
+
+use crate::{timing::Part, vis::Drawn};
+
+/// A day as driven by the runner.
+///
+/// [`AdventOfCodeDay`] panics on invalid input and always solves with the
+/// puzzle defaults. This adds fallible parsing and parameters other than the
+/// defaults on top. Days without parameters reject every override.
+pub trait RunnableDay: AdventOfCodeDay {
+    type Params: Default + Sync;
+
+    /// `false` only for [`Unsolved`], which the runner skips.
+    const SOLVED: bool = true;
+
+    /// Parses the input, reporting the line and column of invalid input.
+    fn try_parse_input(input: &str) -> eyre::Result<Self::ParsedInput<'_>>;
+
+    fn set_param(_params: &mut Self::Params, key: &str, _value: &str) -> eyre::Result<()> {
+        bail!("unknown parameter {key}, this day has no parameters")
+    }
+
+    /// Checks that the parsed input fits the parameters, e.g. that its
+    /// coordinates lie inside a grid of the configured size.
+    fn check_input(_input: &Self::ParsedInput<'_>, _params: &Self::Params) -> eyre::Result<()> {
+        Ok(())
+    }
+
+    fn solve_part1_with(input: &Self::ParsedInput<'_>, params: &Self::Params) -> Self::Part1Output;
+
+    fn solve_part2_with(input: &Self::ParsedInput<'_>, params: &Self::Params) -> Self::Part2Output;
+
+    /// Size of the real puzzle input in the unit used by [`RunnableDay::generate`].
+    const GEN_SIZE: usize;
+
+    /// Generates a random input that `try_parse_input` accepts, see the `gen`
+    /// module of the day for what `size` means.
+    fn generate<R: Rng>(rng: &mut R, size: usize) -> String;
+
+    /// Passes the intermediate states of `part` to `frame`, see the `vis`
+    /// module of the day for what is drawn.
+    fn visualise(
+        _input: &Self::ParsedInput<'_>,
+        _params: &Self::Params,
+        _part: Part,
+        _frame: &mut dyn FnMut(&Grid),
+    ) -> eyre::Result<()> {
+        bail!("this day has no visualisation")
+    }
+}
+
+/// Stands in for the days of a [`crate::Year`] that have no crate yet.
+#[derive(Default)]
+pub struct Unsolved;
+
+impl AdventOfCodeDay for Unsolved {
+    type ParsedInput<'a> = ();
+    type Part1Output = String;
+    type Part2Output = String;
+
+    fn parse_input(_: &str) -> Self::ParsedInput<'_> {
+        panic!("this day is not solved yet")
+    }
+
+    fn solve_part1(_: &Self::ParsedInput<'_>) -> Self::Part1Output {
+        unreachable!("unsolved days cannot be parsed")
+    }
+
+    fn solve_part2(_: &Self::ParsedInput<'_>) -> Self::Part2Output {
+        unreachable!("unsolved days cannot be parsed")
+    }
+}
+
+impl RunnableDay for Unsolved {
+    type Params = ();
+
+    const SOLVED: bool = false;
+
+    fn try_parse_input(_: &str) -> eyre::Result<Self::ParsedInput<'_>> {
+        bail!("this day is not solved yet")
+    }
+
+    fn solve_part1_with(input: &Self::ParsedInput<'_>, _: &()) -> Self::Part1Output {
+        Self::solve_part1(input)
+    }
+
+    fn solve_part2_with(input: &Self::ParsedInput<'_>, _: &()) -> Self::Part2Output {
+        Self::solve_part2(input)
+    }
+
+    const GEN_SIZE: usize = 0;
+
+    fn generate<R: Rng>(_: &mut R, _: usize) -> String {
+        String::new()
+    }
+}
+
+/// The [`RunnableDay::visualise`] of a day listed as `dayNN + vis`, which
+/// forwards to the `part1` and `part2` functions of its `vis` module.
+macro_rules! visualise {
+    (vis, without_params, $day:ident) => {
+        fn visualise(
+            input: &Self::ParsedInput<'_>,
+            _: &(),
+            part: Part,
+            frame: &mut dyn FnMut(&Grid),
+        ) -> eyre::Result<()> {
+            match part {
+                Part::One => $day::vis::part1(input, frame).into_result(),
+                Part::Two => $day::vis::part2(input, frame).into_result(),
+            }
+        }
+    };
+    (vis, with_params, $day:ident) => {
+        fn visualise(
+            input: &Self::ParsedInput<'_>,
+            params: &Self::Params,
+            part: Part,
+            frame: &mut dyn FnMut(&Grid),
+        ) -> eyre::Result<()> {
+            match part {
+                Part::One => $day::vis::part1(input, params, frame).into_result(),
+                Part::Two => $day::vis::part2(input, params, frame).into_result(),
+            }
+        }
+    };
+}
+
+/// The extra methods of a day with parameters, `dayNN + vis` forwards to its
+/// `vis` module and `dayNN + check` to its `Solver::check_input`.
+macro_rules! extension {
+    (vis, $day:ident) => {
+        visualise!(vis, with_params, $day);
+    };
+    (check, $day:ident) => {
+        fn check_input(input: &Self::ParsedInput<'_>, params: &Self::Params) -> eyre::Result<()> {
+            $day::Solver::check_input(input, params)
+        }
+    };
+}
+
+macro_rules! without_params {
+    ($($day:ident $(+ $vis:ident)?),* $(,)?) => {
+        $(
+            impl RunnableDay for $day::Solver {
+                type Params = ();
+
+                fn try_parse_input(input: &str) -> eyre::Result<Self::ParsedInput<'_>> {
+                    $day::Solver::try_parse_input(input)
+                }
+
+                fn solve_part1_with(input: &Self::ParsedInput<'_>, _: &()) -> Self::Part1Output {
+                    Self::solve_part1(input)
+                }
+
+                fn solve_part2_with(input: &Self::ParsedInput<'_>, _: &()) -> Self::Part2Output {
+                    Self::solve_part2(input)
+                }
+
+                const GEN_SIZE: usize = $day::gen::DEFAULT_SIZE;
+
+                fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+                    $day::gen::generate(rng, size)
+                }
+
+                $(visualise!($vis, without_params, $day);)?
+            }
+        )*
+    };
+}
+
+macro_rules! with_params {
+    ($($day:ident $(+ $ext:ident)*),* $(,)?) => {
+        $(
+            impl RunnableDay for $day::Solver {
+                type Params = $day::Params;
+
+                fn try_parse_input(input: &str) -> eyre::Result<Self::ParsedInput<'_>> {
+                    $day::Solver::try_parse_input(input)
+                }
+
+                fn set_param(params: &mut Self::Params, key: &str, value: &str) -> eyre::Result<()> {
+                    params.set(key, value)
+                }
+
+                fn solve_part1_with(
+                    input: &Self::ParsedInput<'_>,
+                    params: &Self::Params,
+                ) -> Self::Part1Output {
+                    $day::Solver::solve_part1_with(input, params)
+                }
+
+                fn solve_part2_with(
+                    input: &Self::ParsedInput<'_>,
+                    params: &Self::Params,
+                ) -> Self::Part2Output {
+                    $day::Solver::solve_part2_with(input, params)
+                }
+
+                const GEN_SIZE: usize = $day::gen::DEFAULT_SIZE;
+
+                fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+                    $day::gen::generate(rng, size)
+                }
+
+                $(extension!($ext, $day);)*
+            }
+        )*
+    };
+}
+
+without_params!(
+    day01,
+    day02,
+    day03,
+    day04,
+    day05,
+    day06 + vis,
+    day07,
+    day08,
+    day09,
+    day10,
+    day12,
+    day13,
+    day15 + vis,
+    day16 + vis,
+    day19,
+    day23,
+    day24,
+    day25,
+);
+with_params!(
+    day11,
+    day14 + vis,
+    day17,
+    day18 + vis + check,
+    day20 + vis,
+    day21,
+    day22
+);
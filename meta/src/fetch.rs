@@ -0,0 +1,278 @@
+use std::{
+    path::PathBuf,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use color_eyre::{
+    eyre::{ensure, eyre, Report, WrapErr},
+    Result,
+};
+use secrecy::{ExposeSecret, SecretString};
+
+/// The puzzle site, overridden to test against a local server.
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Sent with every request so the site can tell who is calling.
+const USER_AGENT: &str = concat!("meta/", env!("CARGO_PKG_VERSION"), " (puzzle input runner)");
+
+/// Keeps requests to the site at least `interval` apart, even across runs of
+/// the runner, by storing the time of the last request in a file.
+pub struct Throttle {
+    pub path: PathBuf,
+    pub interval: Duration,
+}
+
+impl Throttle {
+    /// Time of the last request, `None` if there was none or the file is unreadable.
+    fn last(&self) -> Option<SystemTime> {
+        let millis = std::fs::read_to_string(&self.path)
+            .ok()?
+            .trim()
+            .parse()
+            .ok()?;
+        Some(UNIX_EPOCH + Duration::from_millis(millis))
+    }
+
+    /// Sleeps until `interval` has passed since the last request, then
+    /// records a new one.
+    pub fn wait(&self) -> Result<()> {
+        if let Some(last) = self.last() {
+            let elapsed = SystemTime::now().duration_since(last).unwrap_or_default();
+            if let Some(wait) = self.interval.checked_sub(elapsed) {
+                eprintln!(
+                    "waiting {}s before the next request to the site",
+                    wait.as_secs()
+                );
+                std::thread::sleep(wait);
+            }
+        }
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis();
+        std::fs::write(&self.path, now.to_string())
+            .wrap_err_with(|| format!("cannot write {}", self.path.display()))
+    }
+}
+
+/// Talks to the puzzle site with the session cookie of a logged in user.
+pub struct Site {
+    base_url: String,
+    session: SecretString,
+    throttle: Throttle,
+    agent: ureq::Agent,
+}
+
+impl Site {
+    pub fn new(base_url: &str, session: SecretString, throttle: Throttle) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .timeout(Duration::from_secs(30))
+            .user_agent(USER_AGENT)
+            .build();
+        Site {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session,
+            throttle,
+            agent,
+        }
+    }
+
+    pub fn input_url(&self, year: usize, day: usize) -> String {
+        format!("{}/{year}/day/{day}/input", self.base_url)
+    }
+
+    /// Downloads the puzzle input of `day` of `year`, which is only ever kept
+    /// in memory.
+    pub fn fetch_input(&self, year: usize, day: usize) -> Result<SecretString> {
+        ensure!((1..=25).contains(&day), "day {day} does not exist");
+        let url = self.input_url(year, day);
+        self.throttle.wait()?;
+        let response = self
+            .agent
+            .get(&url)
+            .set(
+                "Cookie",
+                &format!("session={}", self.session.expose_secret()),
+            )
+            .call();
+        match response {
+            Ok(response) => {
+                let input = SecretString::from(
+                    response
+                        .into_string()
+                        .wrap_err_with(|| format!("failed to read the input from {url}"))?,
+                );
+                ensure!(
+                    !input.expose_secret().trim().is_empty(),
+                    "{url} returned an empty input"
+                );
+                Ok(input)
+            }
+            Err(ureq::Error::Status(status, response)) => Err(status_error(&url, status, response)),
+            Err(e) => Err(Report::new(e).wrap_err(format!("failed to request {url}"))),
+        }
+    }
+
+    pub fn answer_url(&self, year: usize, day: usize) -> String {
+        format!("{}/{year}/day/{day}/answer", self.base_url)
+    }
+
+    /// Posts `answer` for part `level` of `day` of `year` and returns the page
+    /// the site answers with.
+    pub fn post_answer(&self, year: usize, day: usize, level: u8, answer: &str) -> Result<String> {
+        ensure!((1..=25).contains(&day), "day {day} does not exist");
+        let url = self.answer_url(year, day);
+        self.throttle.wait()?;
+        let response = self
+            .agent
+            .post(&url)
+            .set(
+                "Cookie",
+                &format!("session={}", self.session.expose_secret()),
+            )
+            .send_form(&[("level", &level.to_string()), ("answer", answer)]);
+        match response {
+            Ok(response) => response
+                .into_string()
+                .wrap_err_with(|| format!("failed to read the response of {url}")),
+            Err(ureq::Error::Status(status, response)) => Err(status_error(&url, status, response)),
+            Err(e) => Err(Report::new(e).wrap_err(format!("failed to post to {url}"))),
+        }
+    }
+}
+
+/// Explains the error responses of the site.
+fn status_error(url: &str, status: u16, response: ureq::Response) -> Report {
+    match status {
+        // the site answers a missing or expired session with one of these
+        400 | 500 => eyre!(
+            "{url} rejected the session cookie with status {status}, log in again and update AOC_SESSION"
+        ),
+        404 => eyre!("{url} was not found, the puzzle is probably not unlocked yet"),
+        429 => match response.header("Retry-After") {
+            Some(secs) => eyre!("{url} is rate limited, try again in {secs} seconds"),
+            None => eyre!("{url} is rate limited, try again later"),
+        },
+        _ => {
+            let body = response.into_string().unwrap_or_default();
+            let reason = body.lines().next().unwrap_or_default();
+            eyre!("{url} failed with status {status}: {reason}")
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        path::PathBuf,
+        thread::JoinHandle,
+        time::Duration,
+    };
+
+    use secrecy::{ExposeSecret, SecretString};
+
+    use super::{Site, Throttle};
+
+    /// A fresh directory for the files of a single test.
+    pub(crate) fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("meta-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// A local stand-in for the site that answers one request per response
+    /// and hands back the requests it received.
+    pub(crate) fn serve(responses: Vec<String>) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            responses
+                .into_iter()
+                .map(|response| {
+                    let (mut stream, _) = listener.accept().unwrap();
+                    let mut reader = BufReader::new(stream.try_clone().unwrap());
+                    let mut request = String::new();
+                    let mut length = 0;
+                    loop {
+                        let mut line = String::new();
+                        reader.read_line(&mut line).unwrap();
+                        if let Some(value) = line.to_lowercase().strip_prefix("content-length:") {
+                            length = value.trim().parse().unwrap();
+                        }
+                        request.push_str(&line);
+                        if line == "\r\n" || line.is_empty() {
+                            break;
+                        }
+                    }
+                    let mut body = vec![0; length];
+                    reader.read_exact(&mut body).unwrap();
+                    request.push_str(&String::from_utf8(body).unwrap());
+                    stream.write_all(response.as_bytes()).unwrap();
+                    request
+                })
+                .collect()
+        });
+        (url, server)
+    }
+
+    pub(crate) fn response(status: &str, headers: &str, body: &str) -> String {
+        format!(
+            "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n{headers}\r\n{body}",
+            body.len()
+        )
+    }
+
+    fn site(url: &str, dir: &std::path::Path) -> Site {
+        let throttle = Throttle {
+            path: dir.join("last-request"),
+            interval: Duration::ZERO,
+        };
+        Site::new(url, SecretString::from("cookie"), throttle)
+    }
+
+    #[test]
+    fn test_fetch_input() {
+        let dir = scratch_dir("fetch");
+        let (url, server) = serve(vec![
+            response("200 OK", "", "1 2\n3 4\n"),
+            response(
+                "404 Not Found",
+                "",
+                "Please don't repeatedly request this endpoint before it unlocks!",
+            ),
+            response(
+                "400 Bad Request",
+                "",
+                "Puzzle inputs differ by user. Please log in to get your puzzle input.",
+            ),
+            response("429 Too Many Requests", "Retry-After: 60\r\n", ""),
+        ]);
+        let site = site(&format!("{url}/"), &dir);
+        let input = site.fetch_input(2024, 1).unwrap();
+        assert_eq!(input.expose_secret(), "1 2\n3 4\n");
+        let error = |day| site.fetch_input(2024, day).unwrap_err().to_string();
+        assert!(error(2).contains("not unlocked"));
+        assert!(error(3).contains("session cookie"));
+        assert!(error(4).contains("try again in 60 seconds"));
+        assert!(error(26).contains("does not exist"));
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /2024/day/1/input HTTP/1.1\r\n"));
+        assert!(requests[0].contains("\r\nCookie: session=cookie\r\n"));
+        assert!(requests[3].starts_with("GET /2024/day/4/input "));
+        assert!(dir.join("last-request").exists());
+    }
+
+    #[test]
+    fn test_throttle() {
+        let dir = scratch_dir("throttle");
+        let throttle = Throttle {
+            path: dir.join("last-request"),
+            interval: Duration::from_millis(300),
+        };
+        throttle.wait().unwrap();
+        let start = std::time::Instant::now();
+        throttle.wait().unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(250));
+    }
+}
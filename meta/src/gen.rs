@@ -0,0 +1,80 @@
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use crate::{day::RunnableDay, visit_day, DayVisitor};
+
+/// Generates a random input for a single day.
+///
+/// The same seed and size always produce the same input, ChaCha is used
+/// because its output is stable across `rand` releases.
+pub struct Generate {
+    pub seed: u64,
+    /// Size in the unit of the day, the size of the real input if `None`.
+    ///
+    /// Sizes below 1 are raised to 1, no puzzle has an empty input.
+    pub size: Option<usize>,
+}
+
+impl DayVisitor for Generate {
+    type Output = String;
+
+    fn visit<D: RunnableDay>(self) -> String {
+        let mut rng = ChaCha8Rng::seed_from_u64(self.seed);
+        D::generate(&mut rng, self.size.unwrap_or(D::GEN_SIZE).max(1))
+    }
+}
+
+/// Generates an input for `day` of `year`, returns `None` if the day does not exist.
+pub fn generate(year: usize, day: usize, seed: u64, size: Option<usize>) -> Option<String> {
+    visit_day(year, day, Generate { seed, size })
+}
+
+#[cfg(test)]
+mod tests {
+    use color_eyre::eyre;
+
+    use super::generate;
+    use crate::{day::RunnableDay, visit_day, DayVisitor, DEFAULT_YEAR};
+
+    struct Parse<'a>(&'a str);
+
+    impl DayVisitor for Parse<'_> {
+        type Output = eyre::Result<()>;
+
+        fn visit<D: RunnableDay>(self) -> eyre::Result<()> {
+            D::try_parse_input(self.0).map(|_| ())
+        }
+    }
+
+    #[test]
+    fn test_generate_parses() {
+        for day in 1..=25 {
+            for (seed, size) in [
+                (0, None),
+                (1, Some(0)),
+                (2, Some(1)),
+                (3, Some(2)),
+                (4, Some(10)),
+            ] {
+                let input = generate(DEFAULT_YEAR, day, seed, size).unwrap();
+                if let Err(e) = visit_day(DEFAULT_YEAR, day, Parse(&input)).unwrap() {
+                    panic!("day {day}, seed {seed}, size {size:?}: {e}\n{input}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_generate_seeded() {
+        assert_eq!(
+            generate(2024, 5, 7, Some(20)),
+            generate(2024, 5, 7, Some(20))
+        );
+        assert_ne!(
+            generate(2024, 5, 7, Some(20)),
+            generate(2024, 5, 8, Some(20))
+        );
+        assert_eq!(generate(2024, 26, 0, None), None);
+        assert_eq!(generate(2023, 5, 0, None), None);
+    }
+}
@@ -0,0 +1,135 @@
+use std::path::{Path, PathBuf};
+
+use color_eyre::{
+    eyre::{eyre, WrapErr},
+    Result,
+};
+use secrecy::SecretString;
+
+/// Secrets used to decrypt `.age` inputs.
+///
+/// Inputs are either encrypted with a shared scrypt passphrase or to the
+/// X25519 recipients of the people working on them, the age header decides
+/// which of the two is used.
+#[derive(Default)]
+pub struct Keys {
+    passphrase: Option<SecretString>,
+    identities: Vec<Box<dyn age::Identity>>,
+}
+
+impl Keys {
+    /// Loads the identities from all `identity_files`.
+    pub fn new(passphrase: Option<SecretString>, identity_files: &[PathBuf]) -> Result<Self> {
+        let mut identities = Vec::new();
+        for file in identity_files {
+            let file_identities = age::IdentityFile::from_file(file.display().to_string())
+                .and_then(|f| {
+                    f.into_identities()
+                        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+                })
+                .wrap_err_with(|| format!("failed to read identity file {}", file.display()))?;
+            identities.extend(file_identities);
+        }
+        Ok(Keys {
+            passphrase,
+            identities,
+        })
+    }
+
+    /// Decrypts a binary or armored age file, using the passphrase or the
+    /// identities depending on its header.
+    pub fn decrypt(&self, ciphertext: &[u8]) -> Result<Vec<u8>> {
+        let decryptor = age::Decryptor::new_buffered(age::armor::ArmoredReader::new(ciphertext))?;
+        let mut reader = if decryptor.is_scrypt() {
+            let passphrase = self
+                .passphrase
+                .clone()
+                .ok_or_else(|| eyre!("Passphrase is required for encrypted input"))?;
+            let identity = age::scrypt::Identity::new(passphrase);
+            decryptor.decrypt(std::iter::once(&identity as &dyn age::Identity))?
+        } else {
+            if self.identities.is_empty() {
+                return Err(eyre!(
+                    "Input is encrypted to recipients, an identity file is required"
+                ));
+            }
+            decryptor.decrypt(self.identities.iter().map(|i| i.as_ref()))?
+        };
+        let mut plaintext = Vec::new();
+        std::io::Read::read_to_end(&mut reader, &mut plaintext)?;
+        Ok(plaintext)
+    }
+}
+
+impl Keys {
+    /// Fails if [`Keys::encrypt`] would have neither recipients nor a passphrase.
+    pub fn ensure_can_encrypt(&self, recipients: &[age::x25519::Recipient]) -> Result<()> {
+        if recipients.is_empty() && self.passphrase.is_none() {
+            return Err(eyre!("Passphrase or recipient is required for encryption"));
+        }
+        Ok(())
+    }
+
+    /// Encrypts `plaintext` to `recipients`, or with the passphrase if there are none.
+    pub fn encrypt(
+        &self,
+        plaintext: &[u8],
+        recipients: &[age::x25519::Recipient],
+    ) -> Result<Vec<u8>> {
+        let encryptor = if recipients.is_empty() {
+            let passphrase = self
+                .passphrase
+                .clone()
+                .ok_or_else(|| eyre!("Passphrase or recipient is required for encryption"))?;
+            age::Encryptor::with_user_passphrase(passphrase)
+        } else {
+            age::Encryptor::with_recipients(recipients.iter().map(|r| r as &dyn age::Recipient))?
+        };
+        let mut ciphertext = Vec::new();
+        let mut writer = encryptor.wrap_output(&mut ciphertext)?;
+        std::io::Write::write_all(&mut writer, plaintext)?;
+        writer.finish()?;
+        Ok(ciphertext)
+    }
+}
+
+const AGE_MAGIC: &[u8] = b"age-encryption.org/";
+const AGE_ARMOR_MAGIC: &[u8] = b"-----BEGIN AGE ENCRYPTED FILE-----";
+
+/// Whether `data` looks like a binary or armored age file.
+pub fn is_age(data: &[u8]) -> bool {
+    let data = data.trim_ascii_start();
+    data.starts_with(AGE_MAGIC) || data.starts_with(AGE_ARMOR_MAGIC)
+}
+
+/// Whether `path` refers to stdin instead of a file.
+pub fn is_stdin(path: &Path) -> bool {
+    path == Path::new("-")
+}
+
+/// Reads a puzzle input from a file or from stdin for `-`.
+///
+/// Encrypted inputs are recognized by their age header and decrypted with `keys`.
+pub fn read_input(path: &Path, keys: &Keys) -> Result<String> {
+    let data = if is_stdin(path) {
+        let mut data = Vec::new();
+        std::io::Read::read_to_end(&mut std::io::stdin().lock(), &mut data)?;
+        data
+    } else {
+        std::fs::read(path)?
+    };
+    let input = if is_age(&data) {
+        keys.decrypt(&data)?
+    } else {
+        data
+    };
+    Ok(String::from_utf8(input)?)
+}
+
+/// Conventional location of the encrypted input for `day` of `year` below the
+/// workspace `root`.
+pub fn input_path(root: &Path, year: usize, day: usize) -> PathBuf {
+    root.join(crate::year_dir(year))
+        .join(format!("day{day:02}"))
+        .join("input.txt.age")
+}
@@ -0,0 +1,73 @@
+use std::{
+    fmt::Display,
+    panic::{catch_unwind, AssertUnwindSafe},
+    sync::Mutex,
+};
+
+/// Message and source location of a caught panic.
+#[derive(Debug, Clone)]
+pub struct Panic {
+    pub message: String,
+    pub location: Option<String>,
+}
+
+impl Display for Panic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.location {
+            Some(location) => write!(f, "{} (at {location})", self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+/// Location of the last panic, written by the hook installed in [`catch_panic`].
+///
+/// This is not a thread local, since solvers may panic on worker threads.
+static LAST_LOCATION: Mutex<Option<String>> = Mutex::new(None);
+
+/// Removes terminal color codes, e.g. from `color_eyre` reports inside panic messages.
+fn strip_ansi(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // skip the escape sequence up to and including its final letter
+            chars.by_ref().find(|c| c.is_ascii_alphabetic());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(msg) = payload.downcast_ref::<&str>() {
+        strip_ansi(msg)
+    } else if let Some(msg) = payload.downcast_ref::<String>() {
+        strip_ansi(msg)
+    } else {
+        "unknown panic payload".to_string()
+    }
+}
+
+/// Runs `f`, returning a panic as an error instead of unwinding further.
+///
+/// The panic hook is silenced while `f` runs, callers are expected to report
+/// the returned [`Panic`] themselves.
+pub fn catch_panic<T>(f: impl FnOnce() -> T) -> Result<T, Panic> {
+    let previous_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(|info| {
+        *LAST_LOCATION.lock().unwrap_or_else(|e| e.into_inner()) =
+            info.location().map(|l| l.to_string());
+    }));
+    let res = catch_unwind(AssertUnwindSafe(f));
+    std::panic::set_hook(previous_hook);
+
+    res.map_err(|payload| Panic {
+        message: panic_message(payload.as_ref()),
+        location: LAST_LOCATION
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take(),
+    })
+}
@@ -1,5 +1,26 @@
-use aoc_traits::AdventOfCodeSolutions;
+use std::{fmt::Display, path::PathBuf};
 
+use aoc_traits::AdventOfCodeSolutions;
+use day::RunnableDay;
+
+#[macro_use]
+pub mod day;
+pub mod fetch;
+pub mod gen;
+pub mod input;
+pub mod isolate;
+pub mod params;
+pub mod report;
+pub mod scaffold;
+pub mod submit;
+pub mod timing;
+pub mod verify;
+pub mod vis;
+
+/// The year the runner uses when no `--year` is given.
+pub const DEFAULT_YEAR: usize = 2024;
+
+/// Advent of Code 2024.
 pub struct AoC2024;
 
 impl AdventOfCodeSolutions for AoC2024 {
@@ -53,3 +74,166 @@ impl AdventOfCodeSolutions for AoC2024 {
 
     type Day25 = day25::Solver;
 }
+
+impl Year for AoC2024 {
+    const YEAR: usize = 2024;
+}
+
+/// A day the runner can solve and print the answers of.
+pub trait Solution: RunnableDay<Part1Output: Display, Part2Output: Display> {}
+
+impl<D> Solution for D
+where
+    D: RunnableDay,
+    D::Part1Output: Display,
+    D::Part2Output: Display,
+{
+}
+
+/// The solutions of one year of the event.
+///
+/// Days a year has no crate for yet are [`day::Unsolved`], which the runner
+/// treats as if they did not exist.
+pub trait Year:
+    AdventOfCodeSolutions<
+    Day01: Solution,
+    Day02: Solution,
+    Day03: Solution,
+    Day04: Solution,
+    Day05: Solution,
+    Day06: Solution,
+    Day07: Solution,
+    Day08: Solution,
+    Day09: Solution,
+    Day10: Solution,
+    Day11: Solution,
+    Day12: Solution,
+    Day13: Solution,
+    Day14: Solution,
+    Day15: Solution,
+    Day16: Solution,
+    Day17: Solution,
+    Day18: Solution,
+    Day19: Solution,
+    Day20: Solution,
+    Day21: Solution,
+    Day22: Solution,
+    Day23: Solution,
+    Day24: Solution,
+    Day25: Solution,
+>
+{
+    const YEAR: usize;
+}
+
+/// A computation that is generic over the solver of a single day.
+///
+/// Rust has no generic closures, so code that needs to call the
+/// [`aoc_traits::AdventOfCodeDay`] methods of a day selected at runtime
+/// implements this trait and passes it to [`visit_day`].
+pub trait DayVisitor {
+    type Output;
+
+    fn visit<D>(self) -> Self::Output
+    where
+        D: day::RunnableDay,
+        D::Part1Output: Display,
+        D::Part2Output: Display;
+}
+
+/// Calls `visitor` with the solver for `day` of the year `Y`.
+///
+/// Returns `None` if the day does not exist or is not solved yet.
+pub fn visit_year<Y: Year, V: DayVisitor>(day: usize, visitor: V) -> Option<V::Output> {
+    match day {
+        1 => visit_solution::<Y::Day01, V>(visitor),
+        2 => visit_solution::<Y::Day02, V>(visitor),
+        3 => visit_solution::<Y::Day03, V>(visitor),
+        4 => visit_solution::<Y::Day04, V>(visitor),
+        5 => visit_solution::<Y::Day05, V>(visitor),
+        6 => visit_solution::<Y::Day06, V>(visitor),
+        7 => visit_solution::<Y::Day07, V>(visitor),
+        8 => visit_solution::<Y::Day08, V>(visitor),
+        9 => visit_solution::<Y::Day09, V>(visitor),
+        10 => visit_solution::<Y::Day10, V>(visitor),
+        11 => visit_solution::<Y::Day11, V>(visitor),
+        12 => visit_solution::<Y::Day12, V>(visitor),
+        13 => visit_solution::<Y::Day13, V>(visitor),
+        14 => visit_solution::<Y::Day14, V>(visitor),
+        15 => visit_solution::<Y::Day15, V>(visitor),
+        16 => visit_solution::<Y::Day16, V>(visitor),
+        17 => visit_solution::<Y::Day17, V>(visitor),
+        18 => visit_solution::<Y::Day18, V>(visitor),
+        19 => visit_solution::<Y::Day19, V>(visitor),
+        20 => visit_solution::<Y::Day20, V>(visitor),
+        21 => visit_solution::<Y::Day21, V>(visitor),
+        22 => visit_solution::<Y::Day22, V>(visitor),
+        23 => visit_solution::<Y::Day23, V>(visitor),
+        24 => visit_solution::<Y::Day24, V>(visitor),
+        25 => visit_solution::<Y::Day25, V>(visitor),
+        _ => None,
+    }
+}
+
+fn visit_solution<D: Solution, V: DayVisitor>(visitor: V) -> Option<V::Output> {
+    D::SOLVED.then(|| visitor.visit::<D>())
+}
+
+/// Builds [`visit_day`] and [`YEARS`] from 2024 and the years listed in the
+/// generated [`years`] module.
+macro_rules! registry {
+    ($($year:ident),* $(,)?) => {
+        /// Calls `visitor` with the solver for `day` of `year`.
+        ///
+        /// Returns `None` if the year or day does not exist or the day is not
+        /// solved yet.
+        pub fn visit_day<V: $crate::DayVisitor>(
+            year: usize,
+            day: usize,
+            visitor: V,
+        ) -> Option<V::Output> {
+            match year {
+                <$crate::AoC2024 as $crate::Year>::YEAR => {
+                    $crate::visit_year::<$crate::AoC2024, V>(day, visitor)
+                }
+                $(<$year as $crate::Year>::YEAR => $crate::visit_year::<$year, V>(day, visitor),)*
+                _ => None,
+            }
+        }
+
+        /// Every year the runner has solutions for.
+        pub const YEARS: &[usize] = &[
+            <$crate::AoC2024 as $crate::Year>::YEAR,
+            $(<$year as $crate::Year>::YEAR,)*
+        ];
+    };
+}
+
+pub mod years;
+
+pub use years::{visit_day, YEARS};
+
+/// Directory of the day crates of `year` relative to the workspace root.
+///
+/// 2024 predates the support for several years and keeps its days at the root.
+pub fn year_dir(year: usize) -> PathBuf {
+    match year {
+        2024 => PathBuf::new(),
+        year => PathBuf::from(format!("aoc{year}")),
+    }
+}
+
+/// Runs `day` of `year` with separate timings for parsing and the selected parts.
+///
+/// Fails if `options` contains parameters the day does not know about or if
+/// the input cannot be parsed. Panics inside the solver are caught and
+/// reported together with the phase they occurred in. Returns `None` if the
+/// day does not exist.
+pub fn time_day(
+    year: usize,
+    day: usize,
+    input: &str,
+    options: &timing::RunOptions,
+) -> Option<Result<timing::DayRun, timing::RunError>> {
+    visit_day(year, day, timing::TimeDay { input, options })
+}
@@ -1,35 +1,933 @@
-use std::path::PathBuf;
+use std::{
+    fs::File,
+    io::BufWriter,
+    path::{Path, PathBuf},
+    time::Duration,
+};
 
-use aoc_traits::AdventOfCodeSolutions;
-use clap::Parser;
-use color_eyre::Result;
-use secrecy::SecretString;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use color_eyre::{
+    eyre::{eyre, Report, WrapErr},
+    Result, Section, SectionExt,
+};
+use itertools::Itertools;
+use meta::{
+    fetch::{Site, Throttle, DEFAULT_BASE_URL},
+    input::{input_path, is_stdin, read_input, Keys},
+    params::Param,
+    report::DayRecord,
+    scaffold::new_day,
+    submit::{level, parse_response, AnswerLog, Attempt, Outcome},
+    timing::{DayRun, Part, PhasePanic, RunError, RunOptions},
+    verify::Answers,
+    year_dir, DEFAULT_YEAR, YEARS,
+};
+use render::{ApngFrames, AsciiFrames, FrameSink, GifFrames, Palette, PngFrames};
+use secrecy::{ExposeSecret, SecretString};
 
 #[derive(Parser)]
+#[clap(subcommand_negates_reqs = true)]
 struct AoCRunner {
+    #[clap(subcommand)]
+    command: Option<Command>,
+    #[clap(short, long, required_unless_present = "all")]
+    day: Option<usize>,
+    /// Puzzle input, plain or age encrypted; `-` or no input reads from stdin
     #[clap(short, long)]
-    day: usize,
-    #[clap(short, long)]
-    input: PathBuf,
-    #[clap(short, long, env = "AGE_PASSPHRASE")]
+    input: Option<PathBuf>,
+    /// Run all days, reading each input from `dayNN/input.txt.age` below `--root`
+    #[clap(long, conflicts_with_all = ["day", "input"])]
+    all: bool,
+    /// Year of the event, the days of years other than 2024 live in `aocYYYY/` below `--root`
+    #[clap(long, default_value_t = DEFAULT_YEAR, global = true)]
+    year: usize,
+    /// Workspace root used to locate the inputs in `--all` mode
+    #[clap(long, default_value = ".", global = true)]
+    root: PathBuf,
+    #[clap(short, long, env = "AGE_PASSPHRASE", global = true)]
     passphrase: Option<SecretString>,
+    /// age identity file for inputs encrypted to recipients, may be given several times
+    #[clap(long = "identity", env = "AGE_IDENTITY", global = true)]
+    identities: Vec<PathBuf>,
+    /// Time parsing and both parts separately instead of only printing the answers,
+    /// with the `parallel` feature also the speedup over a single thread
+    #[clap(long)]
+    time: bool,
+    /// Number of repetitions per day in `--time` mode
+    #[clap(long, default_value_t = 1, requires = "time")]
+    runs: usize,
+    /// Only solve part 1 or part 2
+    #[clap(long, value_name = "1|2")]
+    part: Option<Part>,
+    /// Override a puzzle parameter of the day, e.g. `--param width=11`, may be given several times
+    #[clap(long = "param", value_name = "KEY=VALUE", conflicts_with = "all")]
+    params: Vec<Param>,
+    /// Output format, `json` prints one `{day, part1, part2, timings, error}` object per line
+    #[clap(long, value_enum, default_value_t = OutputFormat::Text)]
+    output: OutputFormat,
 }
 
-fn main() -> Result<()> {
-    let args = AoCRunner::parse();
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    Text,
+    Json,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum FrameFormat {
+    /// Text frames, animated in place when printed to a terminal
+    Ascii,
+    /// One numbered PNG file per frame in the `--output` directory
+    Png,
+    Gif,
+    Apng,
+}
+
+/// How to reach the puzzle site.
+#[derive(Args)]
+struct SiteArgs {
+    /// Value of the `session` cookie of a logged in browser
+    #[clap(long, env = "AOC_SESSION", hide_env_values = true)]
+    session: SecretString,
+    /// Site to talk to, e.g. a local mock server
+    #[clap(long, env = "AOC_BASE_URL", default_value = DEFAULT_BASE_URL)]
+    base_url: String,
+    /// Minimum number of seconds between two requests to the site
+    #[clap(long, default_value_t = 60)]
+    throttle: u64,
+}
+
+impl SiteArgs {
+    /// The site, with the time of the last request kept in `.aoc-last-request` below `root`.
+    fn site(&self, root: &Path) -> Site {
+        let throttle = Throttle {
+            path: root.join(".aoc-last-request"),
+            interval: Duration::from_secs(self.throttle),
+        };
+        Site::new(&self.base_url, self.session.clone(), throttle)
+    }
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run every day listed in an answers manifest and fail on any mismatch
+    ///
+    /// The manifest has one `day part1 part2` line per day, `-` marks an
+    /// unknown answer and lines starting with `#` are comments. Create it with
+    /// `meta encrypt --answers <PLAINTEXT>`.
+    Verify {
+        /// Answers manifest relative to `--root`, `answers.txt.age` next to the days of `--year` if omitted
+        #[clap(long)]
+        answers: Option<PathBuf>,
+        /// Only verify a single day
+        #[clap(short, long)]
+        day: Option<usize>,
+    },
+    /// Encrypt a plaintext input to `dayNN/input.txt.age` below `--root`, or an answers manifest with `--answers`
+    Encrypt {
+        #[clap(short, long, required_unless_present = "answers")]
+        day: Option<usize>,
+        /// Plaintext puzzle input, or answers manifest with `--answers`
+        plaintext: PathBuf,
+        /// Encrypt the answers manifest of `verify` to `answers.txt.age` next to the days of `--year` instead of an input
+        #[clap(long, conflicts_with = "day")]
+        answers: bool,
+        /// Encrypt to this X25519 recipient instead of the passphrase, may be given several times
+        #[clap(long = "recipient")]
+        recipients: Vec<age::x25519::Recipient>,
+        /// Overwrite an existing encrypted input
+        #[clap(long)]
+        force: bool,
+    },
+    /// Download the input of a day and store it encrypted at `dayNN/input.txt.age` below `--root`
+    ///
+    /// The plaintext is only kept in memory. Requests are spaced at least
+    /// `--throttle` seconds apart, the time of the last one is kept in
+    /// `.aoc-last-request` below `--root`.
+    Fetch {
+        #[clap(short, long)]
+        day: usize,
+        #[clap(flatten)]
+        site: SiteArgs,
+        /// Encrypt to this X25519 recipient instead of the passphrase, may be given several times
+        #[clap(long = "recipient")]
+        recipients: Vec<age::x25519::Recipient>,
+        /// Download again even if the encrypted input exists
+        #[clap(long)]
+        force: bool,
+    },
+    /// Solve a part of a day and submit the answer to the site
+    ///
+    /// Every submission is recorded in `--log`. Answers that are already known
+    /// to be wrong, or that lie outside the bounds of earlier too high and too
+    /// low answers, are not submitted.
+    Submit {
+        #[clap(short, long)]
+        day: usize,
+        #[clap(long, value_name = "1|2")]
+        part: Part,
+        /// Puzzle input, `dayNN/input.txt.age` below `--root` if omitted
+        #[clap(short, long)]
+        input: Option<PathBuf>,
+        /// Override a puzzle parameter of the day, e.g. `--param width=11`, may be given several times
+        #[clap(long = "param", value_name = "KEY=VALUE")]
+        params: Vec<Param>,
+        #[clap(flatten)]
+        site: SiteArgs,
+        /// Log of all submissions, relative to `--root`
+        #[clap(long, default_value = ".aoc-answers.jsonl")]
+        log: PathBuf,
+    },
+    /// Generate a random input in the shape of the puzzle input of a day
+    Gen {
+        #[clap(short, long)]
+        day: usize,
+        /// Seed of the random generator, the same seed gives the same input
+        #[clap(long, default_value_t = 0)]
+        seed: u64,
+        /// Size of the input in the unit of the day, e.g. lines or grid side, defaults to the size of the real input
+        #[clap(long)]
+        size: Option<usize>,
+        /// Where to write the input, stdout if omitted
+        output: Option<PathBuf>,
+    },
+    /// Draw the intermediate states of a day that simulates something on a grid
+    Vis {
+        #[clap(short, long)]
+        day: usize,
+        /// Puzzle input, plain or age encrypted; `-` or no input reads from stdin
+        #[clap(short, long)]
+        input: Option<PathBuf>,
+        #[clap(long, value_name = "1|2", default_value = "1")]
+        part: Part,
+        #[clap(long, value_enum, default_value_t = FrameFormat::Ascii)]
+        format: FrameFormat,
+        /// Directory for `png`, file for `gif` and `apng`, stdout for `ascii` if omitted
+        #[clap(short, long, required_if_eq_any = [("format", "png"), ("format", "gif"), ("format", "apng")])]
+        output: Option<PathBuf>,
+        /// Colour overrides per tile, e.g. `#=404040,.=000000`
+        #[clap(long, default_value = "", value_parser = parse_palette)]
+        palette: Box<Palette>,
+        /// Width and height of a tile in pixels
+        #[clap(long, default_value_t = 4, value_parser = clap::value_parser!(u16).range(1..))]
+        scale: u16,
+        /// Only draw every nth frame, the last frame is always drawn
+        #[clap(long, default_value_t = 1, value_parser = clap::value_parser!(u16).range(1..))]
+        every: u16,
+        /// Time each frame is shown in milliseconds
+        #[clap(long, default_value_t = 100)]
+        delay: u64,
+        /// Override a puzzle parameter of the day, e.g. `--param width=11`, may be given several times
+        #[clap(long = "param", value_name = "KEY=VALUE")]
+        params: Vec<Param>,
+    },
+    /// Create the crate of a new day, e.g. `meta new --year 2025 --day 1`
+    ///
+    /// The crate is created at `aocYYYY/dayNN` below `--root` with an empty
+    /// solver and ignored tests, added to the workspace members and the
+    /// dependencies of meta and registered in `meta/src/years.rs`.
+    New {
+        #[clap(short, long)]
+        day: usize,
+    },
+    /// Decrypt `dayNN/input.txt.age` below `--root` to a file or stdout
+    Decrypt {
+        #[clap(short, long)]
+        day: usize,
+        /// Where to write the plaintext, stdout if omitted
+        output: Option<PathBuf>,
+        /// Allow writing the plaintext inside `--root`
+        #[clap(long)]
+        force: bool,
+    },
+}
+
+const DAYS: usize = 25;
+
+enum DayStatus {
+    Solved(DayRun),
+    Failed(Report),
+    Panicked(PhasePanic),
+    MissingInput,
+}
+
+impl std::fmt::Display for DayStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DayStatus::Solved(_) => write!(f, "ok"),
+            DayStatus::Failed(e) => write!(f, "failed: {e}"),
+            DayStatus::Panicked(panic) => {
+                // panic messages often contain multi-line reports, keep the summary compact
+                let message = panic.panic.message.split_whitespace().join(" ");
+                let message = match message.char_indices().nth(60) {
+                    Some((idx, _)) => format!("{}...", &message[..idx]),
+                    None => message,
+                };
+                write!(f, "panicked in {}: {message}", panic.phase)
+            }
+            DayStatus::MissingInput => write!(f, "missing input"),
+        }
+    }
+}
+
+impl DayStatus {
+    fn to_record(&self, day: usize) -> DayRecord {
+        match self {
+            DayStatus::Solved(run) => DayRecord::from_run(day, run),
+            DayStatus::Panicked(panic) => DayRecord::from_error(day, panic),
+            status => DayRecord::from_error(day, status),
+        }
+    }
+
+    /// Turns a failed day into a report naming the day, the phase and the input.
+    fn into_report(self, day: usize, path: &Path) -> Option<Report> {
+        let input = match is_stdin(path) {
+            true => "stdin".to_string(),
+            false => path.display().to_string(),
+        };
+        match self {
+            DayStatus::Solved(_) => None,
+            DayStatus::Failed(e) => Some(e.wrap_err(format!("day {day} failed for input {input}"))),
+            DayStatus::Panicked(panic) => {
+                let report = eyre!("{}", panic.panic.message).wrap_err(format!(
+                    "day {day} panicked in {} of input {input}",
+                    panic.phase,
+                ));
+                Some(match panic.panic.location {
+                    Some(location) => report.section(location.header("Panic location:")),
+                    None => report,
+                })
+            }
+            DayStatus::MissingInput => {
+                Some(eyre!("day {day} has no input, {input} does not exist"))
+            }
+        }
+    }
+}
+
+fn not_implemented(year: usize, day: usize) -> Report {
+    match YEARS.contains(&year) {
+        true => eyre!("day {day} of {year} is not implemented"),
+        false => eyre!(
+            "there are no solutions for {year}, only for {}",
+            YEARS.iter().join(", ")
+        ),
+    }
+}
 
-    let input = if args.input.extension().map(|e| e == "age").unwrap_or(false) {
-        let age_passphrase = args
-            .passphrase
-            .ok_or_else(|| color_eyre::eyre::eyre!("Passphrase is required for encrypted input"))?;
-        let identity = age::scrypt::Identity::new(age_passphrase);
-        let enc_input = std::fs::read(&args.input)?;
-        String::from_utf8(age::decrypt(&identity, &enc_input)?)?
+fn print_record(record: &DayRecord) -> Result<()> {
+    println!("{}", serde_json::to_string(record)?);
+    Ok(())
+}
+
+fn run_day(
+    year: usize,
+    day: usize,
+    path: &Path,
+    keys: &Keys,
+    options: &RunOptions,
+    quiet: bool,
+) -> DayStatus {
+    if !is_stdin(path) && !path.exists() {
+        return DayStatus::MissingInput;
+    }
+    let input = match read_input(path, keys) {
+        Ok(input) => input,
+        Err(e) => return DayStatus::Failed(e),
+    };
+
+    if !quiet {
+        println!("Day {day:02}");
+    }
+    match meta::time_day(year, day, &input, options) {
+        Some(Ok(run)) => {
+            if !quiet {
+                print_answers(&run);
+            }
+            DayStatus::Solved(run)
+        }
+        Some(Err(RunError::Panic(panic))) => DayStatus::Panicked(panic),
+        Some(Err(RunError::Params(e) | RunError::Parse(e))) => DayStatus::Failed(e),
+        None => DayStatus::Failed(not_implemented(year, day)),
+    }
+}
+
+fn print_answers(run: &DayRun) {
+    if let Some(part1) = &run.part1 {
+        println!("Part 1: {part1}");
+    }
+    if let Some(part2) = &run.part2 {
+        println!("Part 2: {part2}");
+    }
+}
+
+fn fmt_duration(d: Duration) -> String {
+    format!("{d:.2?}")
+}
+
+fn print_timings(run: &DayRun) {
+    println!(
+        "{:>6} | {:>12} | {:>12} | {:>12}",
+        "phase", "min", "median", "max"
+    );
+    println!("{:-<7}+{:-<14}+{:-<14}+{:-<13}", "", "", "", "");
+    for (phase, samples) in [
+        ("parse", &run.timings.parse),
+        ("part1", &run.timings.part1),
+        ("part2", &run.timings.part2),
+    ] {
+        if samples.is_empty() {
+            continue;
+        }
+        println!(
+            "{phase:>6} | {:>12} | {:>12} | {:>12}",
+            fmt_duration(samples.min()),
+            fmt_duration(samples.median()),
+            fmt_duration(samples.max()),
+        );
+    }
+    println!(
+        "{:>6} | {:>12} |",
+        "total",
+        fmt_duration(run.timings.total())
+    );
+    for (phase, parallel, single) in [
+        (
+            "part1",
+            &run.timings.part1,
+            &run.timings.part1_single_thread,
+        ),
+        (
+            "part2",
+            &run.timings.part2,
+            &run.timings.part2_single_thread,
+        ),
+    ] {
+        if single.is_empty() || parallel.is_empty() {
+            continue;
+        }
+        let speedup = single.median().as_secs_f64() / parallel.median().as_secs_f64();
+        println!(
+            "{phase:>6} | {:>12} on a single thread, {speedup:.2}x speedup",
+            fmt_duration(single.median())
+        );
+    }
+}
+
+fn run_all(
+    root: &Path,
+    year: usize,
+    keys: &Keys,
+    options: &RunOptions,
+    time: bool,
+    output: OutputFormat,
+) -> Result<()> {
+    let json = output == OutputFormat::Json;
+    let mut results = Vec::new();
+    for day in 1..=DAYS {
+        let status = run_day(year, day, &input_path(root, year, day), keys, options, json);
+        if json {
+            print_record(&status.to_record(day))?;
+        }
+        results.push((day, status));
+    }
+
+    if !json {
+        print_summary(&results, time);
+    }
+
+    let mut failed = 0;
+    for (day, status) in results {
+        let missing = matches!(status, DayStatus::MissingInput);
+        if let Some(report) = status.into_report(day, &input_path(root, year, day)) {
+            failed += 1;
+            // missing inputs are already obvious from the summary
+            if !missing {
+                eprintln!("{report:?}");
+            }
+        }
+    }
+    if failed > 0 {
+        return Err(eyre!("{failed} of {DAYS} days did not succeed"));
+    }
+    Ok(())
+}
+
+fn print_summary(results: &[(usize, DayStatus)], time: bool) {
+    println!();
+    if time {
+        println!(
+            "{:>3} | {:>12} | {:>12} | {:>12} | {:>12} | status",
+            "day", "parse", "part1", "part2", "total"
+        );
+        println!(
+            "{:-<4}+{:-<14}+{:-<14}+{:-<14}+{:-<14}+{:-<40}",
+            "", "", "", "", "", ""
+        );
     } else {
-        std::fs::read_to_string(&args.input)?
+        println!("{:>3} | {:>12} | status", "day", "time");
+        println!("{:-<4}+{:-<14}+{:-<40}", "", "", "");
+    }
+    for (day, status) in results {
+        match status {
+            DayStatus::Solved(run) if time => println!(
+                "{day:>3} | {:>12} | {:>12} | {:>12} | {:>12} | {status}",
+                fmt_duration(run.timings.parse.median()),
+                fmt_duration(run.timings.part1.median()),
+                fmt_duration(run.timings.part2.median()),
+                fmt_duration(run.timings.total()),
+            ),
+            DayStatus::Solved(run) => println!(
+                "{day:>3} | {:>12} | {status}",
+                fmt_duration(run.timings.total())
+            ),
+            _ if time => println!(
+                "{day:>3} | {:>12} | {:>12} | {:>12} | {:>12} | {status}",
+                "-", "-", "-", "-"
+            ),
+            _ => println!("{day:>3} | {:>12} | {status}", "-"),
+        }
+    }
+    if time {
+        let total: Duration = results
+            .iter()
+            .filter_map(|(_, status)| match status {
+                DayStatus::Solved(run) => Some(run.timings.total()),
+                _ => None,
+            })
+            .sum();
+        println!(
+            "{:-<4}+{:-<14}+{:-<14}+{:-<14}+{:-<14}+{:-<40}",
+            "", "", "", "", "", ""
+        );
+        println!(
+            "{:>3} | {:>12} | {:>12} | {:>12} | {:>12} |",
+            "all",
+            "",
+            "",
+            "",
+            fmt_duration(total)
+        );
+    }
+}
+
+/// Default answers manifest of `verify` relative to `--root`.
+fn answers_path(year: usize) -> PathBuf {
+    year_dir(year).join("answers.txt.age")
+}
+
+fn verify(
+    root: &Path,
+    year: usize,
+    answers: &Path,
+    only_day: Option<usize>,
+    keys: &Keys,
+) -> Result<()> {
+    let path = root.join(answers);
+    let answers: Answers = read_input(&path, keys)
+        .wrap_err_with(|| format!("cannot read answers manifest {}", path.display()))
+        .with_suggestion(|| {
+            "create it from a file with one `day part1 part2` line per day \
+             using `meta encrypt --answers <PLAINTEXT>`"
+        })?
+        .parse()?;
+
+    let mut results = Vec::new();
+    let mut reports = Vec::new();
+    for day in answers
+        .days()
+        .filter(|&d| only_day.is_none_or(|only| only == d))
+    {
+        let expected = answers.get(day).expect("day is listed in the manifest");
+        let path = input_path(root, year, day);
+        let problems = match run_day(year, day, &path, keys, &RunOptions::default(), false) {
+            DayStatus::Solved(run) => {
+                let (Some(part1), Some(part2)) = (&run.part1, &run.part2) else {
+                    unreachable!("verify runs both parts");
+                };
+                expected.mismatches(part1, part2)
+            }
+            status => {
+                let problem = status.to_string();
+                reports.extend(status.into_report(day, &path));
+                vec![problem]
+            }
+        };
+        results.push((day, problems));
+    }
+    if results.is_empty() {
+        return Err(eyre!("no days to verify"));
+    }
+
+    println!();
+    println!("{:>3} | status", "day");
+    println!("{:-<4}+{:-<40}", "", "");
+    for (day, problems) in &results {
+        if problems.is_empty() {
+            println!("{day:>3} | ok");
+        }
+        for problem in problems {
+            println!("{day:>3} | {problem}");
+        }
+    }
+
+    for report in &reports {
+        eprintln!("{report:?}");
+    }
+    let failed = results.iter().filter(|(_, p)| !p.is_empty()).count();
+    if failed > 0 {
+        return Err(eyre!(
+            "{failed} of {} days failed verification",
+            results.len()
+        ));
+    }
+    Ok(())
+}
+
+/// Encrypts `plaintext` and writes it to `target`, creating its directory.
+fn write_encrypted(
+    target: &Path,
+    plaintext: &[u8],
+    recipients: &[age::x25519::Recipient],
+    keys: &Keys,
+) -> Result<()> {
+    let ciphertext = keys.encrypt(plaintext, recipients)?;
+    std::fs::create_dir_all(target.parent().expect("input path has a parent"))?;
+    std::fs::write(target, ciphertext)?;
+    eprintln!("wrote {}", target.display());
+    Ok(())
+}
+
+fn encrypt(
+    target: &Path,
+    plaintext: &Path,
+    recipients: &[age::x25519::Recipient],
+    force: bool,
+    keys: &Keys,
+) -> Result<()> {
+    if target.exists() && !force {
+        return Err(eyre!(
+            "{} already exists, use --force to overwrite it",
+            target.display()
+        ));
+    }
+    let plaintext = std::fs::read(plaintext)?;
+    write_encrypted(target, &plaintext, recipients, keys)
+}
+
+fn fetch(
+    root: &Path,
+    year: usize,
+    day: usize,
+    site: &Site,
+    recipients: &[age::x25519::Recipient],
+    force: bool,
+    keys: &Keys,
+) -> Result<()> {
+    let target = input_path(root, year, day);
+    if target.exists() && !force {
+        return Err(eyre!(
+            "{} already exists, use --force to download it again",
+            target.display()
+        ));
+    }
+    // fail before spending a request on an input that cannot be stored
+    keys.ensure_can_encrypt(recipients)?;
+    let input = site.fetch_input(year, day)?;
+    write_encrypted(&target, input.expose_secret().as_bytes(), recipients, keys)
+}
+
+/// Whether `path`, which does not need to exist yet, is located below `root`.
+fn is_inside(path: &Path, root: &Path) -> Result<bool> {
+    let root = root.canonicalize()?;
+    let path = std::path::absolute(path)?;
+    // canonicalize the longest existing prefix, the rest cannot contain symlinks
+    let existing = path
+        .ancestors()
+        .find(|p| p.exists())
+        .expect("the filesystem root exists");
+    let rest = path.strip_prefix(existing).expect("ancestor is a prefix");
+    Ok(existing.canonicalize()?.join(rest).starts_with(root))
+}
+
+fn decrypt(
+    root: &Path,
+    year: usize,
+    day: usize,
+    output: Option<&Path>,
+    force: bool,
+    keys: &Keys,
+) -> Result<()> {
+    let input = read_input(&input_path(root, year, day), keys)?;
+    match output {
+        Some(output) => {
+            if !force && is_inside(output, root)? {
+                return Err(eyre!(
+                    "refusing to write plaintext input to {} inside {}, use --force to do it anyway",
+                    output.display(),
+                    root.display()
+                ));
+            }
+            std::fs::write(output, input)?;
+        }
+        None => print!("{input}"),
+    }
+    Ok(())
+}
+
+fn submit(
+    year: usize,
+    day: usize,
+    part: Part,
+    input: &str,
+    params: &[Param],
+    site: &Site,
+    log: &mut AnswerLog,
+) -> Result<()> {
+    let options = RunOptions {
+        part: Some(part),
+        params: params.to_vec(),
+        ..Default::default()
     };
+    let run = match meta::time_day(year, day, input, &options) {
+        Some(Ok(run)) => run,
+        Some(Err(RunError::Panic(panic))) => return Err(eyre!("day {day} {panic}")),
+        Some(Err(RunError::Params(e) | RunError::Parse(e))) => return Err(e),
+        None => return Err(not_implemented(year, day)),
+    };
+    let answer = match part {
+        Part::One => run.part1,
+        Part::Two => run.part2,
+    }
+    .expect("the selected part was solved");
+    log.check(year, day, part, &answer)
+        .wrap_err_with(|| format!("not submitting {answer} for day {day} part {}", level(part)))?;
+
+    let page = site.post_answer(year, day, level(part), &answer)?;
+    let outcome = parse_response(&page);
+    log.record(Attempt::new(year, day, part, &answer, outcome.clone()))?;
+    println!("Day {day:02} part {}: {answer} is {outcome}", level(part));
+    match outcome {
+        Outcome::Correct => Ok(()),
+        outcome => Err(eyre!("{answer} was not accepted: {outcome}")),
+    }
+}
+
+fn gen(
+    year: usize,
+    day: usize,
+    seed: u64,
+    size: Option<usize>,
+    output: Option<&Path>,
+) -> Result<()> {
+    let input =
+        meta::gen::generate(year, day, seed, size).ok_or_else(|| not_implemented(year, day))?;
+    match output {
+        Some(output) => std::fs::write(output, input)?,
+        None => print!("{input}"),
+    }
+    Ok(())
+}
+
+/// Boxed, the palette is by far the largest argument of all subcommands.
+fn parse_palette(s: &str) -> Result<Box<Palette>> {
+    s.parse().map(Box::new)
+}
+
+struct VisOptions {
+    format: FrameFormat,
+    output: Option<PathBuf>,
+    palette: Palette,
+    scale: usize,
+    delay: Duration,
+}
 
-    meta::AoC2024::solve_day(args.day, &input).map_err(|e| color_eyre::eyre::eyre!(e))?;
+fn frame_sink(options: VisOptions) -> Result<Box<dyn FrameSink>> {
+    let VisOptions {
+        format,
+        output,
+        palette,
+        scale,
+        delay,
+    } = options;
+    let create = |path: &Path| -> Result<BufWriter<File>> {
+        let file =
+            File::create(path).map_err(|e| eyre!("cannot create {}: {e}", path.display()))?;
+        Ok(BufWriter::new(file))
+    };
+    Ok(match (format, output) {
+        (FrameFormat::Ascii, None) => Box::new(AsciiFrames::stdout(delay)),
+        (FrameFormat::Ascii, Some(path)) => Box::new(AsciiFrames::new(create(&path)?)),
+        (FrameFormat::Png, Some(dir)) => Box::new(PngFrames::new(dir, palette, scale)?),
+        (FrameFormat::Gif, Some(path)) => {
+            Box::new(GifFrames::new(create(&path)?, palette, scale, delay))
+        }
+        (FrameFormat::Apng, Some(path)) => {
+            Box::new(ApngFrames::new(create(&path)?, palette, scale, delay))
+        }
+        (_, None) => unreachable!("clap requires --output for image formats"),
+    })
+}
 
+fn vis(
+    year: usize,
+    day: usize,
+    input: &str,
+    part: Part,
+    params: &[Param],
+    every: usize,
+    options: VisOptions,
+) -> Result<()> {
+    let sink = frame_sink(options)?;
+    let frames = meta::vis::visualise(year, day, input, part, params, every, sink)
+        .ok_or_else(|| not_implemented(year, day))?
+        .map_err(|e| e.wrap_err(format!("cannot visualise day {day}")))?;
+    eprintln!("drew {frames} frames");
     Ok(())
 }
+
+fn main() -> Result<()> {
+    color_eyre::install()?;
+    let args = AoCRunner::parse();
+    let keys = Keys::new(args.passphrase.clone(), &args.identities)?;
+
+    match &args.command {
+        Some(Command::Verify { answers, day }) => {
+            let answers = answers.clone().unwrap_or_else(|| answers_path(args.year));
+            return verify(&args.root, args.year, &answers, *day, &keys);
+        }
+        Some(Command::Encrypt {
+            day,
+            plaintext,
+            answers,
+            recipients,
+            force,
+        }) => {
+            let target = match (answers, day) {
+                (true, _) => args.root.join(answers_path(args.year)),
+                (false, Some(day)) => input_path(&args.root, args.year, *day),
+                (false, None) => unreachable!("clap requires --day without --answers"),
+            };
+            return encrypt(&target, plaintext, recipients, *force, &keys);
+        }
+        Some(Command::Fetch {
+            day,
+            site,
+            recipients,
+            force,
+        }) => {
+            let site = site.site(&args.root);
+            return fetch(
+                &args.root, args.year, *day, &site, recipients, *force, &keys,
+            );
+        }
+        Some(Command::Submit {
+            day,
+            part,
+            input,
+            params,
+            site,
+            log,
+        }) => {
+            let path = input
+                .clone()
+                .unwrap_or_else(|| input_path(&args.root, args.year, *day));
+            let input = read_input(&path, &keys)?;
+            let mut log = AnswerLog::open(args.root.join(log))?;
+            return submit(
+                args.year,
+                *day,
+                *part,
+                &input,
+                params,
+                &site.site(&args.root),
+                &mut log,
+            );
+        }
+        Some(Command::Gen {
+            day,
+            seed,
+            size,
+            output,
+        }) => return gen(args.year, *day, *seed, *size, output.as_deref()),
+        Some(Command::Vis {
+            day,
+            input,
+            part,
+            format,
+            output,
+            palette,
+            scale,
+            every,
+            delay,
+            params,
+        }) => {
+            let path = input.clone().unwrap_or_else(|| PathBuf::from("-"));
+            let options = VisOptions {
+                format: *format,
+                output: output.clone(),
+                palette: Palette::clone(palette),
+                scale: usize::from(*scale),
+                delay: Duration::from_millis(*delay),
+            };
+            let input = read_input(&path, &keys)?;
+            return vis(
+                args.year,
+                *day,
+                &input,
+                *part,
+                params,
+                usize::from(*every),
+                options,
+            );
+        }
+        Some(Command::New { day }) => {
+            for path in new_day(&args.root, args.year, *day)? {
+                eprintln!("wrote {}", path.display());
+            }
+            return Ok(());
+        }
+        Some(Command::Decrypt { day, output, force }) => {
+            return decrypt(
+                &args.root,
+                args.year,
+                *day,
+                output.as_deref(),
+                *force,
+                &keys,
+            );
+        }
+        None => {}
+    }
+
+    let options = RunOptions {
+        runs: args.runs,
+        part: args.part,
+        params: args.params,
+        single_thread: args.time && cfg!(feature = "parallel"),
+    };
+    if args.all {
+        return run_all(
+            &args.root,
+            args.year,
+            &keys,
+            &options,
+            args.time,
+            args.output,
+        );
+    }
+
+    let day = args.day.expect("clap enforces --day without --all");
+    let path = args.input.unwrap_or_else(|| PathBuf::from("-"));
+    let json = args.output == OutputFormat::Json;
+    let status = run_day(args.year, day, &path, &keys, &options, json);
+    if json {
+        print_record(&status.to_record(day))?;
+    } else if let (DayStatus::Solved(run), true) = (&status, args.time) {
+        print_timings(run);
+    }
+    match status.into_report(day, &path) {
+        Some(report) => Err(report),
+        None => Ok(()),
+    }
+}
@@ -0,0 +1,61 @@
+use std::str::FromStr;
+
+use color_eyre::eyre::{self, bail, eyre, WrapErr};
+
+use crate::day::RunnableDay;
+
+/// A `key=value` override for one of the parameters of a day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Param {
+    pub key: String,
+    pub value: String,
+}
+
+impl FromStr for Param {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (key, value) = s
+            .split_once('=')
+            .ok_or_else(|| eyre!("expected `key=value`, got {s:?}"))?;
+        let key = key.trim();
+        if key.is_empty() {
+            bail!("empty parameter name in {s:?}");
+        }
+        Ok(Param {
+            key: key.to_string(),
+            value: value.trim().to_string(),
+        })
+    }
+}
+
+/// Builds the parameters of `D` from its defaults and `overrides`.
+pub fn params<D: RunnableDay>(overrides: &[Param]) -> eyre::Result<D::Params> {
+    let mut params = D::Params::default();
+    for Param { key, value } in overrides {
+        D::set_param(&mut params, key, value)
+            .wrap_err_with(|| format!("invalid parameter {key}={value}"))?;
+    }
+    Ok(params)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{params, Param};
+
+    #[test]
+    fn test_parse() {
+        let param: Param = "steps=100".parse().unwrap();
+        assert_eq!(param.key, "steps");
+        assert_eq!(param.value, "100");
+        assert!("steps".parse::<Param>().is_err());
+        assert!("=100".parse::<Param>().is_err());
+
+        let overrides = ["width=11".parse().unwrap(), "height=7".parse().unwrap()];
+        let p = params::<day14::Solver>(&overrides).unwrap();
+        assert_eq!((p.width, p.height, p.steps), (11, 7, 100));
+        assert!(params::<day14::Solver>(&["depth=1".parse().unwrap()]).is_err());
+        assert!(params::<day14::Solver>(&["width=x".parse().unwrap()]).is_err());
+        assert!(params::<day01::Solver>(&overrides).is_err());
+    }
+}
@@ -0,0 +1,74 @@
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::timing::{DayRun, Samples};
+
+/// Machine readable result of running a single day.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DayRecord {
+    pub day: usize,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+    pub timings: Option<TimingsRecord>,
+    pub error: Option<String>,
+}
+
+impl DayRecord {
+    pub fn from_run(day: usize, run: &DayRun) -> Self {
+        DayRecord {
+            day,
+            part1: run.part1.clone(),
+            part2: run.part2.clone(),
+            timings: Some(TimingsRecord {
+                parse: (&run.timings.parse).into(),
+                part1: phase_record(&run.timings.part1),
+                part2: phase_record(&run.timings.part2),
+                total_ns: nanos(run.timings.total()),
+            }),
+            error: None,
+        }
+    }
+
+    pub fn from_error(day: usize, error: impl ToString) -> Self {
+        DayRecord {
+            day,
+            error: Some(error.to_string()),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TimingsRecord {
+    pub parse: PhaseRecord,
+    /// `None` if the part was not run.
+    pub part1: Option<PhaseRecord>,
+    pub part2: Option<PhaseRecord>,
+    pub total_ns: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PhaseRecord {
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub max_ns: u64,
+}
+
+fn nanos(d: Duration) -> u64 {
+    u64::try_from(d.as_nanos()).unwrap_or(u64::MAX)
+}
+
+fn phase_record(samples: &Samples) -> Option<PhaseRecord> {
+    (!samples.is_empty()).then(|| samples.into())
+}
+
+impl From<&Samples> for PhaseRecord {
+    fn from(samples: &Samples) -> Self {
+        PhaseRecord {
+            min_ns: nanos(samples.min()),
+            median_ns: nanos(samples.median()),
+            max_ns: nanos(samples.max()),
+        }
+    }
+}
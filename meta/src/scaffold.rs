@@ -0,0 +1,405 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write,
+    path::{Path, PathBuf},
+};
+
+use color_eyre::{
+    eyre::{bail, ensure, eyre, WrapErr},
+    Result,
+};
+
+use crate::year_dir;
+
+/// The first year of the event.
+const FIRST_YEAR: usize = 2015;
+
+/// Location of the generated registry below the workspace root.
+pub const YEARS_RS: &str = "meta/src/years.rs";
+
+const CARGO_TOML: &str = r#"[package]
+name = "CRATE"
+version = "0.1.0"
+edition = "2021"
+
+# See more keys and their definitions at https://doc.rust-lang.org/cargo/reference/manifest.html
+
+[dependencies]
+aoc-traits = { workspace = true }
+parsing = { workspace = true }
+eyre = "0.6.12"
+rand = "0.8.5"
+"#;
+
+const LIB_RS: &str = r#"pub mod gen;
+
+use aoc_traits::AdventOfCodeDay;
+use parsing::error_at;
+
+#[derive(Default)]
+pub struct Solver;
+impl Solver {
+    pub fn try_parse_input(input: &str) -> Result<Vec<&str>, eyre::Report> {
+        input
+            .lines()
+            .map(|line| match line.trim().is_empty() {
+                true => Err(error_at(input, line, "expected a non-empty line")),
+                false => Ok(line),
+            })
+            .collect()
+    }
+}
+
+impl AdventOfCodeDay for Solver {
+    type ParsedInput<'a> = Vec<&'a str>;
+    type Part1Output = usize;
+    type Part2Output = usize;
+
+    fn parse_input(input: &str) -> Self::ParsedInput<'_> {
+        Self::try_parse_input(input).expect("Failed to parse input")
+    }
+
+    // placeholders until the puzzle is solved, so that running every day works
+    fn solve_part1(_input: &Self::ParsedInput<'_>) -> Self::Part1Output {
+        0
+    }
+    fn solve_part2(_input: &Self::ParsedInput<'_>) -> Self::Part2Output {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_traits::AdventOfCodeDay;
+
+    use crate::Solver;
+    const TEST_INPUT: &str = "";
+
+    #[test]
+    #[ignore = "the example of the puzzle is not filled in yet"]
+    fn test_stage1() {
+        let parsed = Solver::parse_input(TEST_INPUT);
+        assert_eq!(Solver::solve_part1(&parsed), 0);
+    }
+    #[test]
+    #[ignore = "the example of the puzzle is not filled in yet"]
+    fn test_stage2() {
+        let parsed = Solver::parse_input(TEST_INPUT);
+        assert_eq!(Solver::solve_part2(&parsed), 0);
+    }
+}
+"#;
+
+const GEN_RS: &str = r#"//! Random inputs in the shape of the puzzle input.
+
+use rand::Rng;
+
+/// Number of lines in the real puzzle input.
+pub const DEFAULT_SIZE: usize = 1000;
+
+/// Generates `size` lines of random input.
+///
+/// A placeholder of random numbers until the format of the puzzle is known.
+pub fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+    (0..size)
+        .map(|_| format!("{}\n", rng.gen_range(0..1000)))
+        .collect()
+}
+"#;
+
+/// Name of the package of `day` in `year`, e.g. `aoc2025-day01`.
+fn package_name(year: usize, day: usize) -> String {
+    format!("aoc{year}-day{day:02}")
+}
+
+/// The days with a crate below `aocYYYY/dayNN` in the workspace at `root`.
+pub fn find_days(root: &Path) -> Result<BTreeMap<usize, BTreeSet<usize>>> {
+    let mut years = BTreeMap::new();
+    let entries =
+        std::fs::read_dir(root).wrap_err_with(|| format!("cannot list {}", root.display()))?;
+    for entry in entries {
+        let entry = entry?;
+        let Some(year) = number_after(&entry.file_name(), "aoc") else {
+            continue;
+        };
+        for day_entry in std::fs::read_dir(entry.path())? {
+            let day_entry = day_entry?;
+            let Some(day) = number_after(&day_entry.file_name(), "day") else {
+                continue;
+            };
+            if (1..=25).contains(&day) && day_entry.path().join("Cargo.toml").exists() {
+                years.entry(year).or_insert_with(BTreeSet::new).insert(day);
+            }
+        }
+    }
+    Ok(years)
+}
+
+/// Parses names like `aoc2025` or `day07` with the given `prefix`.
+fn number_after(name: &std::ffi::OsStr, prefix: &str) -> Option<usize> {
+    let digits = name.to_str()?.strip_prefix(prefix)?;
+    match digits.bytes().all(|b| b.is_ascii_digit()) {
+        true => digits.parse().ok(),
+        false => None,
+    }
+}
+
+/// A macro call on one line if rustfmt keeps it there, one argument per line
+/// otherwise.
+fn macro_call(name: &str, args: &[String]) -> String {
+    let joined = args.join(", ");
+    if joined.len() <= 60 {
+        return format!("{name}!({joined});\n");
+    }
+    let mut out = format!("{name}!(\n");
+    for arg in args {
+        writeln!(out, "    {arg},").unwrap();
+    }
+    out.push_str(");\n");
+    out
+}
+
+/// The source of the [`crate::years`] module for `years`.
+pub fn render_years(years: &BTreeMap<usize, BTreeSet<usize>>) -> String {
+    let mut out = String::from(
+        "//! Years added with `meta new`.
+//!
+//! Generated from the `aocYYYY/dayNN` crates of the workspace, `meta new`
+//! rewrites this file whenever it adds a day.
+",
+    );
+    if !years.is_empty() {
+        let unsolved = years.values().any(|days| days.len() < 25);
+        out.push_str(
+            "
+use aoc_traits::{AdventOfCodeDay, AdventOfCodeSolutions};
+use color_eyre::eyre;
+use rand::Rng;
+",
+        );
+        out.push_str(match unsolved {
+            true => "\nuse crate::{\n    day::{RunnableDay, Unsolved},\n    Year,\n};\n",
+            false => "\nuse crate::{day::RunnableDay, Year};\n",
+        });
+    }
+    let names: Vec<_> = years.keys().map(|year| format!("AoC{year}")).collect();
+    out.push('\n');
+    out.push_str(&macro_call("registry", &names));
+
+    for (year, days) in years {
+        write!(
+            out,
+            "
+/// Advent of Code {year}.
+pub struct AoC{year};
+
+impl AdventOfCodeSolutions for AoC{year} {{
+"
+        )
+        .unwrap();
+        for day in 1..=25 {
+            if day > 1 {
+                out.push('\n');
+            }
+            match days.contains(&day) {
+                true => writeln!(out, "    type Day{day:02} = aoc{year}_day{day:02}::Solver;"),
+                false => writeln!(out, "    type Day{day:02} = Unsolved;"),
+            }
+            .unwrap();
+        }
+        write!(
+            out,
+            "}}
+
+impl Year for AoC{year} {{
+    const YEAR: usize = {year};
+}}
+
+"
+        )
+        .unwrap();
+        let crates: Vec<_> = days
+            .iter()
+            .map(|day| format!("aoc{year}_day{day:02}"))
+            .collect();
+        out.push_str(&macro_call("without_params", &crates));
+    }
+    out
+}
+
+/// Adds `member` to the `members` of the workspace manifest, keeping them sorted.
+fn add_member(manifest: &str, member: &str) -> Result<String> {
+    let lines: Vec<_> = manifest.lines().collect();
+    let start = lines
+        .iter()
+        .position(|line| line.trim() == "members = [")
+        .ok_or_else(|| eyre!("no `members = [` line in the workspace manifest"))?
+        + 1;
+    let len = lines[start..]
+        .iter()
+        .position(|line| line.trim() == "]")
+        .ok_or_else(|| eyre!("the workspace members are not closed by a `]` line"))?;
+    let mut members = lines[start..start + len].to_vec();
+    let entry = format!("    \"{member}\",");
+    members.push(&entry);
+    members.sort();
+
+    let mut out = lines[..start].join("\n");
+    for line in members.iter().chain(&lines[start + len..]) {
+        out.push('\n');
+        out.push_str(line);
+    }
+    out.push('\n');
+    Ok(out)
+}
+
+/// Appends `dependency` to the `[dependencies]` of a package manifest.
+fn add_dependency(manifest: &str, dependency: &str) -> Result<String> {
+    let lines: Vec<_> = manifest.lines().collect();
+    let start = lines
+        .iter()
+        .position(|line| line.trim() == "[dependencies]")
+        .ok_or_else(|| eyre!("no [dependencies] in the manifest of meta"))?;
+    let end = lines[start + 1..]
+        .iter()
+        .position(|line| line.starts_with('['))
+        .map_or(lines.len(), |idx| start + 1 + idx);
+    // after the last dependency, before the blank lines separating the next table
+    let insert = lines[..end]
+        .iter()
+        .rposition(|line| !line.trim().is_empty())
+        .expect("the [dependencies] line is not blank")
+        + 1;
+
+    let mut out: Vec<_> = lines[..insert].to_vec();
+    out.push(dependency);
+    out.extend(&lines[insert..]);
+    Ok(out.join("\n") + "\n")
+}
+
+/// Creates the crate of `day` in `year` below the workspace `root`, adds it to
+/// the workspace and to the dependencies of `meta` and regenerates
+/// [`crate::years`]. Returns the files that were written.
+pub fn new_day(root: &Path, year: usize, day: usize) -> Result<Vec<PathBuf>> {
+    ensure!((1..=25).contains(&day), "day {day} does not exist");
+    ensure!(year >= FIRST_YEAR, "there was no event in {year}");
+    if year_dir(year).as_os_str().is_empty() {
+        bail!("the days of {year} live at the workspace root and are registered by hand");
+    }
+    let relative = year_dir(year).join(format!("day{day:02}"));
+    let dir = root.join(&relative);
+    ensure!(!dir.exists(), "{} already exists", dir.display());
+    let member = relative
+        .to_str()
+        .ok_or_else(|| eyre!("{} is not valid UTF-8", relative.display()))?
+        .replace('\\', "/");
+    let package = package_name(year, day);
+
+    // edit everything in memory first so that a malformed manifest leaves no half done day behind
+    let workspace_manifest = root.join("Cargo.toml");
+    let meta_manifest = root.join("meta/Cargo.toml");
+    let read = |path: &Path| {
+        std::fs::read_to_string(path).wrap_err_with(|| format!("cannot read {}", path.display()))
+    };
+    let workspace = add_member(&read(&workspace_manifest)?, &member)?;
+    let meta = add_dependency(
+        &read(&meta_manifest)?,
+        &format!("{package} = {{ path = \"../{member}\" }}"),
+    )?;
+    let mut years = find_days(root)?;
+    years.entry(year).or_default().insert(day);
+
+    let files = [
+        (
+            dir.join("Cargo.toml"),
+            CARGO_TOML.replace("CRATE", &package),
+        ),
+        (dir.join("src/lib.rs"), LIB_RS.to_string()),
+        (dir.join("src/gen.rs"), GEN_RS.to_string()),
+        (workspace_manifest, workspace),
+        (meta_manifest, meta),
+        (root.join(YEARS_RS), render_years(&years)),
+    ];
+    std::fs::create_dir_all(dir.join("src"))
+        .wrap_err_with(|| format!("cannot create {}", dir.display()))?;
+    for (path, contents) in &files {
+        std::fs::write(path, contents)
+            .wrap_err_with(|| format!("cannot write {}", path.display()))?;
+    }
+    Ok(files.into_iter().map(|(path, _)| path).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::{add_dependency, add_member, find_days, new_day, render_years, YEARS_RS};
+    use crate::fetch::tests::scratch_dir;
+
+    #[test]
+    fn test_years_up_to_date() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+        let expected = render_years(&find_days(root).unwrap());
+        let actual = std::fs::read_to_string(root.join(YEARS_RS)).unwrap();
+        assert_eq!(actual, expected, "{YEARS_RS} is out of date");
+    }
+
+    #[test]
+    fn test_edit_manifests() {
+        let workspace =
+            "[workspace]\n\nmembers = [\n    \"day01\",\n    \"meta\",\n]\nresolver = \"2\"\n";
+        assert_eq!(
+            add_member(workspace, "aoc2025/day02").unwrap(),
+            "[workspace]\n\nmembers = [\n    \"aoc2025/day02\",\n    \"day01\",\n    \"meta\",\n]\nresolver = \"2\"\n"
+        );
+        assert!(add_member("[workspace]\n", "aoc2025/day02").is_err());
+
+        let meta = "[dependencies]\nday01 = { path = \"../day01\" }\n\n[features]\nparallel = []\n";
+        assert_eq!(
+            add_dependency(meta, "b = \"1\"").unwrap(),
+            "[dependencies]\nday01 = { path = \"../day01\" }\nb = \"1\"\n\n[features]\nparallel = []\n"
+        );
+        assert_eq!(
+            add_dependency("[dependencies]\n", "b = \"1\"").unwrap(),
+            "[dependencies]\nb = \"1\"\n"
+        );
+    }
+
+    #[test]
+    fn test_new_day() {
+        let root = scratch_dir("new-day");
+        std::fs::create_dir_all(root.join("meta/src")).unwrap();
+        std::fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\n    \"meta\",\n]\n",
+        )
+        .unwrap();
+        std::fs::write(root.join("meta/Cargo.toml"), "[dependencies]\n").unwrap();
+
+        new_day(&root, 2025, 3).unwrap();
+        new_day(&root, 2025, 1).unwrap();
+        assert!(new_day(&root, 2025, 1).is_err());
+        assert!(new_day(&root, 2024, 4).is_err());
+        assert!(new_day(&root, 2025, 26).is_err());
+
+        let read = |path: &str| std::fs::read_to_string(root.join(path)).unwrap();
+        assert!(read("aoc2025/day01/Cargo.toml").contains("name = \"aoc2025-day01\""));
+        assert!(read("aoc2025/day03/src/lib.rs").contains("pub struct Solver;"));
+        for file in ["src/lib.rs", "src/gen.rs"] {
+            assert!(!read(&format!("aoc2025/day03/{file}")).contains("todo!"));
+        }
+        assert_eq!(
+            read("Cargo.toml"),
+            "[workspace]\nmembers = [\n    \"aoc2025/day01\",\n    \"aoc2025/day03\",\n    \"meta\",\n]\n"
+        );
+        assert_eq!(
+            read("meta/Cargo.toml"),
+            "[dependencies]\naoc2025-day03 = { path = \"../aoc2025/day03\" }\naoc2025-day01 = { path = \"../aoc2025/day01\" }\n"
+        );
+        let years = read(YEARS_RS);
+        assert!(years.contains("registry!(AoC2025);"));
+        assert!(years.contains("type Day01 = aoc2025_day01::Solver;"));
+        assert!(years.contains("type Day02 = Unsolved;"));
+        assert!(years.contains("without_params!(aoc2025_day01, aoc2025_day03);"));
+    }
+}
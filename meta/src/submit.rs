@@ -0,0 +1,399 @@
+use std::{
+    fmt::Display,
+    io::Write,
+    path::PathBuf,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use color_eyre::{
+    eyre::{eyre, WrapErr},
+    Result,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::timing::Part;
+
+/// What the site said about a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint in which direction.
+    Wrong,
+    /// Answered too soon after the last wrong answer, nothing was checked.
+    Wait {
+        seconds: u64,
+    },
+    /// The part is already solved or not unlocked yet, nothing was checked.
+    WrongLevel,
+    /// A page we do not understand, with its text.
+    Unknown {
+        message: String,
+    },
+}
+
+impl Outcome {
+    /// Whether the site actually judged the answer.
+    fn judged(&self) -> bool {
+        matches!(
+            self,
+            Outcome::Correct | Outcome::TooHigh | Outcome::TooLow | Outcome::Wrong
+        )
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "correct"),
+            Outcome::TooHigh => write!(f, "wrong, too high"),
+            Outcome::TooLow => write!(f, "wrong, too low"),
+            Outcome::Wrong => write!(f, "wrong"),
+            Outcome::Wait { seconds } => write!(f, "answered too recently, wait {seconds}s"),
+            Outcome::WrongLevel => write!(f, "part already solved or not unlocked yet"),
+            Outcome::Unknown { message } => write!(f, "unexpected response: {message}"),
+        }
+    }
+}
+
+/// The text of the `<article>` the site puts its verdict in, without tags.
+fn article_text(page: &str) -> &str {
+    let start = page.find("<article").unwrap_or(0);
+    let end = page[start..]
+        .find("</article>")
+        .map_or(page.len(), |end| start + end);
+    &page[start..end]
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Parses a wait like `1m 23s` or `45s`.
+fn parse_wait(text: &str) -> Option<u64> {
+    let (_, rest) = text.split_once("You have ")?;
+    let (wait, _) = rest.split_once(" left to wait")?;
+    wait.split_whitespace().try_fold(0, |total, unit| {
+        let (value, factor) = match unit.as_bytes().last()? {
+            b'h' => (&unit[..unit.len() - 1], 3600),
+            b'm' => (&unit[..unit.len() - 1], 60),
+            b's' => (&unit[..unit.len() - 1], 1),
+            _ => return None,
+        };
+        Some(total + value.parse::<u64>().ok()? * factor)
+    })
+}
+
+/// Reads the verdict from the page the site returns for a submitted answer.
+pub fn parse_response(page: &str) -> Outcome {
+    let text = strip_tags(article_text(page));
+    if text.contains("That's the right answer") {
+        Outcome::Correct
+    } else if text.contains("That's not the right answer") {
+        if text.contains("your answer is too high") {
+            Outcome::TooHigh
+        } else if text.contains("your answer is too low") {
+            Outcome::TooLow
+        } else {
+            Outcome::Wrong
+        }
+    } else if text.contains("You gave an answer too recently") {
+        // the site always names the wait, a minute is a safe guess otherwise
+        Outcome::Wait {
+            seconds: parse_wait(&text).unwrap_or(60),
+        }
+    } else if text.contains("You don't seem to be solving the right level") {
+        Outcome::WrongLevel
+    } else {
+        Outcome::Unknown { message: text }
+    }
+}
+
+/// A single submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attempt {
+    /// Seconds since the Unix epoch.
+    pub time: u64,
+    pub year: usize,
+    pub day: usize,
+    pub part: u8,
+    pub answer: String,
+    #[serde(flatten)]
+    pub outcome: Outcome,
+}
+
+impl Attempt {
+    pub fn new(year: usize, day: usize, part: Part, answer: &str, outcome: Outcome) -> Self {
+        Attempt {
+            time: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            year,
+            day,
+            part: level(part),
+            answer: answer.to_string(),
+            outcome,
+        }
+    }
+}
+
+/// The number the site uses for `part`.
+pub fn level(part: Part) -> u8 {
+    match part {
+        Part::One => 1,
+        Part::Two => 2,
+    }
+}
+
+/// Every answer ever submitted from this checkout, one JSON [`Attempt`] per line.
+pub struct AnswerLog {
+    pub path: PathBuf,
+    attempts: Vec<Attempt>,
+}
+
+impl AnswerLog {
+    /// Reads the log at `path`, which does not need to exist yet.
+    pub fn open(path: PathBuf) -> Result<Self> {
+        let attempts = match std::fs::read_to_string(&path) {
+            Ok(log) => log
+                .lines()
+                .enumerate()
+                .filter(|(_, line)| !line.trim().is_empty())
+                .map(|(idx, line)| {
+                    serde_json::from_str(line)
+                        .wrap_err_with(|| format!("{}:{}: invalid entry", path.display(), idx + 1))
+                })
+                .collect::<Result<_>>()?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e).wrap_err_with(|| format!("cannot read {}", path.display())),
+        };
+        Ok(AnswerLog { path, attempts })
+    }
+
+    fn attempts(&self, year: usize, day: usize, part: Part) -> impl Iterator<Item = &Attempt> {
+        self.attempts
+            .iter()
+            .filter(move |a| a.year == year && a.day == day && a.part == level(part))
+    }
+
+    /// Fails if submitting `answer` is pointless according to earlier
+    /// attempts, or if the site asked us to wait and the time is not up yet.
+    pub fn check(&self, year: usize, day: usize, part: Part, answer: &str) -> Result<()> {
+        let mut too_high: Option<(i128, &str)> = None;
+        let mut too_low: Option<(i128, &str)> = None;
+        for attempt in self.attempts(year, day, part) {
+            match &attempt.outcome {
+                Outcome::Correct => {
+                    return Err(eyre!(
+                        "day {day} part {} is already solved with {}",
+                        attempt.part,
+                        attempt.answer
+                    ));
+                }
+                outcome if outcome.judged() && attempt.answer == answer => {
+                    return Err(eyre!("{answer} was already submitted and is {outcome}"));
+                }
+                Outcome::TooHigh => {
+                    if let Ok(bound) = attempt.answer.parse() {
+                        if too_high.is_none_or(|(high, _)| bound < high) {
+                            too_high = Some((bound, &attempt.answer));
+                        }
+                    }
+                }
+                Outcome::TooLow => {
+                    if let Ok(bound) = attempt.answer.parse() {
+                        if too_low.is_none_or(|(low, _)| bound > low) {
+                            too_low = Some((bound, &attempt.answer));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        if let Ok(value) = answer.parse::<i128>() {
+            if let Some((_, high_answer)) = too_high.filter(|&(high, _)| value >= high) {
+                return Err(eyre!(
+                    "{answer} cannot be right, {high_answer} was already too high"
+                ));
+            }
+            if let Some((_, low_answer)) = too_low.filter(|&(low, _)| value <= low) {
+                return Err(eyre!(
+                    "{answer} cannot be right, {low_answer} was already too low"
+                ));
+            }
+        }
+        if let Some(until) = self.wait_until(year, day, part) {
+            if let Ok(left) = until.duration_since(SystemTime::now()) {
+                return Err(eyre!(
+                    "the site asked to wait, try again in {}s",
+                    left.as_secs() + 1
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// When the wait the site asked for after the last attempt is over.
+    fn wait_until(&self, year: usize, day: usize, part: Part) -> Option<SystemTime> {
+        match self.attempts(year, day, part).last()? {
+            Attempt {
+                time,
+                outcome: Outcome::Wait { seconds },
+                ..
+            } => Some(UNIX_EPOCH + Duration::from_secs(time + seconds)),
+            _ => None,
+        }
+    }
+
+    /// Appends `attempt` to the log file.
+    pub fn record(&mut self, attempt: Attempt) -> Result<()> {
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .wrap_err_with(|| format!("cannot open {}", self.path.display()))?;
+        writeln!(file, "{}", serde_json::to_string(&attempt)?)?;
+        self.attempts.push(attempt);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use secrecy::SecretString;
+
+    use super::{parse_response, AnswerLog, Attempt, Outcome};
+    use crate::{
+        fetch::{
+            tests::{response, scratch_dir, serve},
+            Site, Throttle,
+        },
+        timing::Part,
+    };
+
+    fn page(article: &str) -> String {
+        format!("<html><body><main>\n<article><p>{article}</p></article>\n</main></body></html>")
+    }
+
+    #[test]
+    fn test_parse_response() {
+        let cases = [
+            (
+                "That's the right answer! You are <em>one gold star</em> closer.",
+                Outcome::Correct,
+            ),
+            (
+                "That's not the right answer; your answer is too high. If you're stuck, ...",
+                Outcome::TooHigh,
+            ),
+            (
+                "That's not the right answer; your answer is too low.",
+                Outcome::TooLow,
+            ),
+            (
+                "That's not the right answer. If you're stuck, make sure you're using the full input data",
+                Outcome::Wrong,
+            ),
+            (
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 23s left to wait.",
+                Outcome::Wait { seconds: 83 },
+            ),
+            (
+                "You gave an answer too recently. You have 45s left to wait.",
+                Outcome::Wait { seconds: 45 },
+            ),
+            (
+                "You don't seem to be solving the right level.  Did you already complete it?",
+                Outcome::WrongLevel,
+            ),
+            (
+                "Something <b>else</b>",
+                Outcome::Unknown {
+                    message: "Something else".to_string(),
+                },
+            ),
+        ];
+        for (article, expected) in cases {
+            assert_eq!(parse_response(&page(article)), expected, "{article}");
+        }
+    }
+
+    #[test]
+    fn test_answer_log() {
+        let path = scratch_dir("answer-log").join("answers.jsonl");
+        let mut log = AnswerLog::open(path.clone()).unwrap();
+        log.check(2024, 1, Part::One, "50").unwrap();
+        for (answer, outcome) in [
+            ("100", Outcome::TooHigh),
+            ("10", Outcome::TooLow),
+            ("42", Outcome::Wrong),
+        ] {
+            log.record(Attempt::new(2024, 1, Part::One, answer, outcome))
+                .unwrap();
+        }
+
+        let log = AnswerLog::open(path.clone()).unwrap();
+        assert!(log.check(2024, 1, Part::One, "50").is_ok());
+        assert!(log.check(2024, 1, Part::Two, "100").is_ok());
+        assert!(log.check(2023, 1, Part::One, "100").is_ok());
+        for refused in ["100", "150", "10", "-3", "42"] {
+            assert!(log.check(2024, 1, Part::One, refused).is_err(), "{refused}");
+        }
+
+        let mut log = log;
+        log.record(Attempt::new(
+            2024,
+            1,
+            Part::One,
+            "50",
+            Outcome::Wait { seconds: 60 },
+        ))
+        .unwrap();
+        assert!(log
+            .check(2024, 1, Part::One, "51")
+            .unwrap_err()
+            .to_string()
+            .contains("wait"));
+        log.record(Attempt::new(2024, 1, Part::One, "51", Outcome::Correct))
+            .unwrap();
+        assert!(log.check(2024, 1, Part::One, "52").is_err());
+        assert_eq!(AnswerLog::open(path).unwrap().attempts.len(), 5);
+    }
+
+    #[test]
+    fn test_post_answer() {
+        let dir = scratch_dir("post-answer");
+        let (url, server) = serve(vec![response(
+            "200 OK",
+            "",
+            &page("That's the right answer!"),
+        )]);
+        let throttle = Throttle {
+            path: dir.join("last-request"),
+            interval: Duration::ZERO,
+        };
+        let site = Site::new(&url, SecretString::from("cookie"), throttle);
+        let page = site.post_answer(2024, 3, 2, "1234").unwrap();
+        assert_eq!(parse_response(&page), Outcome::Correct);
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("POST /2024/day/3/answer HTTP/1.1\r\n"));
+        assert!(requests[0].contains("\r\nCookie: session=cookie\r\n"));
+        assert!(requests[0].ends_with("\r\n\r\nlevel=2&answer=1234"));
+    }
+}
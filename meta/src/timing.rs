@@ -0,0 +1,304 @@
+use std::{
+    fmt::Display,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use color_eyre::eyre::{self, eyre};
+
+use crate::{
+    day::RunnableDay,
+    isolate::{catch_panic, Panic},
+    params::{params, Param},
+    DayVisitor,
+};
+
+/// Repeated measurements of a single phase of a solver.
+#[derive(Debug, Clone, Default)]
+pub struct Samples(Vec<Duration>);
+
+impl Samples {
+    fn push(&mut self, sample: Duration) {
+        self.0.push(sample);
+    }
+
+    fn sorted(&self) -> Vec<Duration> {
+        let mut samples = self.0.clone();
+        samples.sort();
+        samples
+    }
+
+    /// Whether the phase never ran, e.g. because only the other part was selected.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn min(&self) -> Duration {
+        self.0.iter().copied().min().unwrap_or_default()
+    }
+
+    pub fn max(&self) -> Duration {
+        self.0.iter().copied().max().unwrap_or_default()
+    }
+
+    pub fn median(&self) -> Duration {
+        let samples = self.sorted();
+        match samples.len() {
+            0 => Duration::ZERO,
+            n if n % 2 == 1 => samples[n / 2],
+            n => (samples[n / 2 - 1] + samples[n / 2]) / 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Timings {
+    pub parse: Samples,
+    pub part1: Samples,
+    pub part2: Samples,
+    /// The parts once more on a single thread, see [`RunOptions::single_thread`].
+    pub part1_single_thread: Samples,
+    pub part2_single_thread: Samples,
+}
+
+impl Timings {
+    /// Sum of the median times of all three phases.
+    pub fn total(&self) -> Duration {
+        self.parse.median() + self.part1.median() + self.part2.median()
+    }
+}
+
+/// Answers and timings of a day, parts that were not selected are `None`.
+#[derive(Debug, Clone)]
+pub struct DayRun {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+    pub timings: Timings,
+}
+
+/// One of the two parts of a puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl FromStr for Part {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(eyre!("expected part 1 or 2, got {s:?}")),
+        }
+    }
+}
+
+/// The individual steps of solving a day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Phase::Parse => write!(f, "parsing"),
+            Phase::Part1 => write!(f, "part 1"),
+            Phase::Part2 => write!(f, "part 2"),
+        }
+    }
+}
+
+/// A panic inside one of the phases of a solver.
+#[derive(Debug, Clone)]
+pub struct PhasePanic {
+    pub phase: Phase,
+    pub panic: Panic,
+}
+
+impl Display for PhasePanic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "panicked in {}: {}", self.phase, self.panic)
+    }
+}
+
+/// Runs `f` and adds its time to `samples`, without the time it takes to
+/// install and restore the panic hook around it.
+fn timed<T>(samples: &mut Samples, phase: Phase, f: impl FnOnce() -> T) -> Result<T, PhasePanic> {
+    let (res, elapsed) = catch_panic(|| {
+        let start = Instant::now();
+        let res = f();
+        (res, start.elapsed())
+    })
+    .map_err(|panic| PhasePanic { phase, panic })?;
+    samples.push(elapsed);
+    Ok(res)
+}
+
+/// Why a day could not be run to completion.
+#[derive(Debug)]
+pub enum RunError {
+    /// The parameter overrides do not apply to the day or the input does not
+    /// fit them.
+    Params(eyre::Report),
+    /// The parser rejected the input.
+    Parse(eyre::Report),
+    Panic(PhasePanic),
+}
+
+impl From<PhasePanic> for RunError {
+    fn from(panic: PhasePanic) -> Self {
+        RunError::Panic(panic)
+    }
+}
+
+/// How a day is run by [`TimeDay`].
+#[derive(Debug, Clone, Default)]
+pub struct RunOptions {
+    /// Number of repetitions, at least one run is always made.
+    pub runs: usize,
+    /// Only solve this part, both if `None`.
+    pub part: Option<Part>,
+    /// Overrides for the puzzle parameters of the day.
+    pub params: Vec<Param>,
+    /// Also time the parts on a single thread, to see how much the `parallel`
+    /// feature speeds them up. Does nothing without the feature.
+    pub single_thread: bool,
+}
+
+/// Runs all phases of a day `runs` times, timing each phase separately.
+///
+/// Fails without running anything if the parameter overrides do not apply to
+/// the day, and on the first run if the input cannot be parsed.
+pub struct TimeDay<'a> {
+    pub input: &'a str,
+    pub options: &'a RunOptions,
+}
+
+impl DayVisitor for TimeDay<'_> {
+    type Output = Result<DayRun, RunError>;
+
+    fn visit<D>(self) -> Self::Output
+    where
+        D: RunnableDay,
+        D::Part1Output: Display,
+        D::Part2Output: Display,
+    {
+        let params = params::<D>(&self.options.params).map_err(RunError::Params)?;
+        let day_run = run::<D>(self.input, self.options, &params)?;
+        #[cfg(feature = "parallel")]
+        let day_run = match self.options.single_thread {
+            true => time_single_thread::<D>(day_run, self.input, self.options, &params)?,
+            false => day_run,
+        };
+        Ok(day_run)
+    }
+}
+
+/// Adds the timings of the parts on a single thread to `day_run`.
+#[cfg(feature = "parallel")]
+fn time_single_thread<D>(
+    mut day_run: DayRun,
+    input: &str,
+    options: &RunOptions,
+    params: &D::Params,
+) -> Result<DayRun, RunError>
+where
+    D: RunnableDay,
+    D::Part1Output: Display,
+    D::Part2Output: Display,
+{
+    let options = RunOptions {
+        runs: 1,
+        ..options.clone()
+    };
+    let single = single_threaded(|| run::<D>(input, &options, params))?;
+    day_run.timings.part1_single_thread = single.timings.part1;
+    day_run.timings.part2_single_thread = single.timings.part2;
+    Ok(day_run)
+}
+
+/// Runs `f` in a thread pool with a single thread, so that rayon does not
+/// spread the work of the solvers over the other cores.
+#[cfg(feature = "parallel")]
+fn single_threaded<T: Send>(f: impl FnOnce() -> T + Send) -> T {
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(1)
+        .build()
+        .expect("failed to start a thread")
+        .install(f)
+}
+
+fn run<D>(input: &str, options: &RunOptions, params: &D::Params) -> Result<DayRun, RunError>
+where
+    D: RunnableDay,
+    D::Part1Output: Display,
+    D::Part2Output: Display,
+{
+    let mut timings = Timings::default();
+    let (mut part1, mut part2) = (None, None);
+    for _ in 0..options.runs.max(1) {
+        let parsed = timed(&mut timings.parse, Phase::Parse, || {
+            D::try_parse_input(input)
+        })?
+        .map_err(RunError::Parse)?;
+        D::check_input(&parsed, params).map_err(RunError::Params)?;
+        if options.part != Some(Part::Two) {
+            let answer = timed(&mut timings.part1, Phase::Part1, || {
+                D::solve_part1_with(&parsed, params)
+            })?;
+            part1 = Some(answer.to_string());
+        }
+        if options.part != Some(Part::One) {
+            let answer = timed(&mut timings.part2, Phase::Part2, || {
+                D::solve_part2_with(&parsed, params)
+            })?;
+            part2 = Some(answer.to_string());
+        }
+    }
+    Ok(DayRun {
+        part1,
+        part2,
+        timings,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RunError, RunOptions};
+
+    #[test]
+    fn test_input_outside_params() {
+        let options = RunOptions {
+            params: vec!["size=6".parse().unwrap()],
+            ..Default::default()
+        };
+        let run = crate::time_day(2024, 18, "5,4\n6,3\n", &options).unwrap();
+        match run {
+            Err(RunError::Params(e)) => assert_eq!(
+                e.to_string(),
+                "line 2, column 1: coordinate 6,3 outside of the grid"
+            ),
+            other => panic!("expected a parameter error, got {other:?}"),
+        }
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_single_thread() {
+        let options = RunOptions {
+            single_thread: true,
+            ..Default::default()
+        };
+        let run = crate::time_day(2024, 22, "1\n2\n3\n2024\n", &options)
+            .unwrap()
+            .unwrap();
+        assert_eq!(run.part2.as_deref(), Some("23"));
+        assert!(!run.timings.part1_single_thread.is_empty());
+        assert!(!run.timings.part2_single_thread.is_empty());
+    }
+}
@@ -0,0 +1,101 @@
+use std::{collections::BTreeMap, str::FromStr};
+
+use color_eyre::eyre::{self, ensure, eyre};
+
+/// Expected answers for a single day, `None` for parts without a known answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expected {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+impl Expected {
+    /// Describes every part whose answer differs from the expected one.
+    pub fn mismatches(&self, part1: &str, part2: &str) -> Vec<String> {
+        [
+            ("part 1", &self.part1, part1),
+            ("part 2", &self.part2, part2),
+        ]
+        .into_iter()
+        .filter_map(|(name, expected, actual)| match expected {
+            Some(expected) if expected != actual => {
+                Some(format!("{name}: expected {expected}, got {actual}"))
+            }
+            _ => None,
+        })
+        .collect()
+    }
+}
+
+/// Manifest of known answers, one `day part1 part2` line per day.
+///
+/// Answers are separated by whitespace, `-` marks an unknown answer and lines
+/// starting with `#` are comments.
+#[derive(Debug, Clone, Default)]
+pub struct Answers(BTreeMap<usize, Expected>);
+
+impl Answers {
+    pub fn get(&self, day: usize) -> Option<&Expected> {
+        self.0.get(&day)
+    }
+
+    pub fn days(&self) -> impl Iterator<Item = usize> + '_ {
+        self.0.keys().copied()
+    }
+}
+
+impl FromStr for Answers {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut answers = BTreeMap::new();
+        for (idx, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields: Vec<_> = line.split_whitespace().collect();
+            let [day, part1, part2] = fields[..] else {
+                return Err(eyre!(
+                    "line {}: expected `day part1 part2`, got {line:?}",
+                    idx + 1
+                ));
+            };
+            let day: usize = day
+                .parse()
+                .map_err(|e| eyre!("line {}: invalid day {day:?}: {e}", idx + 1))?;
+            let answer = |s: &str| (s != "-").then(|| s.to_string());
+            let expected = Expected {
+                part1: answer(part1),
+                part2: answer(part2),
+            };
+            ensure!(
+                answers.insert(day, expected).is_none(),
+                "line {}: duplicate entry for day {day}",
+                idx + 1
+            );
+        }
+        Ok(Answers(answers))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Answers;
+
+    const TEST_MANIFEST: &str = "# day part1 part2
+1 11 31
+17 4,6,3,5,6,3,5,2,1,0 117440
+25 3 -";
+
+    #[test]
+    fn test_parse() {
+        let answers: Answers = TEST_MANIFEST.parse().unwrap();
+        assert_eq!(answers.days().collect::<Vec<_>>(), [1, 17, 25]);
+        assert!(answers.get(1).unwrap().mismatches("11", "31").is_empty());
+        assert_eq!(answers.get(1).unwrap().mismatches("11", "30").len(), 1);
+        assert!(answers.get(25).unwrap().mismatches("3", "").is_empty());
+        assert!("1 2".parse::<Answers>().is_err());
+        assert!("1 2 3\n1 2 3".parse::<Answers>().is_err());
+    }
+}
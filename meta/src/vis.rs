@@ -0,0 +1,222 @@
+use color_eyre::eyre;
+use grid::Grid;
+use render::FrameSink;
+
+use crate::{
+    day::RunnableDay,
+    params::{params, Param},
+    timing::Part,
+    visit_day, DayVisitor,
+};
+
+/// What the `vis` functions of the days return.
+///
+/// Most of them cannot fail, the ones that only build their grid once the
+/// parameters are known return the errors of doing so.
+pub trait Drawn {
+    fn into_result(self) -> eyre::Result<()>;
+}
+
+impl Drawn for () {
+    fn into_result(self) -> eyre::Result<()> {
+        Ok(())
+    }
+}
+
+impl Drawn for eyre::Result<()> {
+    fn into_result(self) -> eyre::Result<()> {
+        self
+    }
+}
+
+/// Passes every `every`th frame on to a sink, plus the last one so that the
+/// final state is always shown.
+///
+/// The days cannot stop drawing half way, so the first error of the sink is
+/// kept and all frames after it are dropped.
+struct Thinned {
+    sink: Box<dyn FrameSink>,
+    every: usize,
+    seen: usize,
+    written: usize,
+    skipped: Option<Grid>,
+    error: Option<eyre::Report>,
+}
+
+impl Thinned {
+    fn frame(&mut self, grid: &Grid) {
+        if self.error.is_some() {
+            return;
+        }
+        self.seen += 1;
+        if !(self.seen - 1).is_multiple_of(self.every) {
+            self.skipped = Some(grid.clone());
+            return;
+        }
+        self.skipped = None;
+        self.write(grid);
+    }
+
+    fn write(&mut self, grid: &Grid) {
+        match self.sink.frame(grid) {
+            Ok(()) => self.written += 1,
+            Err(e) => self.error = Some(e),
+        }
+    }
+
+    fn finish(mut self) -> eyre::Result<usize> {
+        if let Some(grid) = self.skipped.take() {
+            self.write(&grid);
+        }
+        if let Some(e) = self.error {
+            return Err(e);
+        }
+        self.sink.finish()?;
+        Ok(self.written)
+    }
+}
+
+/// Draws one part of a single day into a [`FrameSink`].
+pub struct Visualise<'a> {
+    pub input: &'a str,
+    pub part: Part,
+    pub params: &'a [Param],
+    /// Only every `every`th frame is drawn, values below 1 are raised to 1.
+    pub every: usize,
+    pub sink: Box<dyn FrameSink>,
+}
+
+impl DayVisitor for Visualise<'_> {
+    /// The number of frames that were drawn.
+    type Output = eyre::Result<usize>;
+
+    fn visit<D: RunnableDay>(self) -> eyre::Result<usize> {
+        let params = params::<D>(self.params)?;
+        let input = D::try_parse_input(self.input)?;
+        D::check_input(&input, &params)?;
+        let mut frames = Thinned {
+            sink: self.sink,
+            every: self.every.max(1),
+            seen: 0,
+            written: 0,
+            skipped: None,
+            error: None,
+        };
+        D::visualise(&input, &params, self.part, &mut |grid| frames.frame(grid))?;
+        frames.finish()
+    }
+}
+
+/// Draws `part` of `day` of `year` for `input`, returns `None` if the day
+/// does not exist.
+pub fn visualise(
+    year: usize,
+    day: usize,
+    input: &str,
+    part: Part,
+    params: &[Param],
+    every: usize,
+    sink: Box<dyn FrameSink>,
+) -> Option<eyre::Result<usize>> {
+    visit_day(
+        year,
+        day,
+        Visualise {
+            input,
+            part,
+            params,
+            every,
+            sink,
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use color_eyre::eyre;
+    use grid::Grid;
+    use render::{AsciiFrames, FrameSink};
+
+    use super::visualise;
+    use crate::timing::Part;
+
+    const DAY06: &str = "....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...
+";
+
+    /// Keeps the frames around so the test can look at them.
+    struct Frames(Rc<RefCell<Vec<String>>>);
+
+    impl FrameSink for Frames {
+        fn frame(&mut self, grid: &Grid) -> eyre::Result<()> {
+            self.0.borrow_mut().push(grid.to_string());
+            Ok(())
+        }
+
+        fn finish(self: Box<Self>) -> eyre::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn frames(day: usize, input: &str, part: Part, every: usize) -> Vec<String> {
+        let frames = Rc::new(RefCell::new(Vec::new()));
+        let sink = Box::new(Frames(frames.clone()));
+        let written = visualise(2024, day, input, part, &[], every, sink)
+            .unwrap()
+            .unwrap();
+        let frames = frames.take();
+        assert_eq!(written, frames.len());
+        frames
+    }
+
+    #[test]
+    fn test_visualise() {
+        let all = frames(6, DAY06, Part::One, 1);
+        assert!(all[0].contains('^'));
+        // every tile the guard walked on in part 1
+        assert_eq!(all.last().unwrap().matches('X').count(), 41);
+
+        let thinned = frames(6, DAY06, Part::One, 10);
+        let mut expected: Vec<_> = all.iter().step_by(10).cloned().collect();
+        if !(all.len() - 1).is_multiple_of(10) {
+            expected.push(all.last().unwrap().clone());
+        }
+        assert_eq!(thinned, expected);
+
+        let loops = frames(6, DAY06, Part::Two, 1);
+        assert_eq!(loops.last().unwrap().matches('O').count(), 6);
+    }
+
+    #[test]
+    fn test_visualise_errors() {
+        let sink = || Box::new(AsciiFrames::new(Vec::new()));
+        assert!(visualise(2024, 1, "1 2\n", Part::One, &[], 1, sink())
+            .unwrap()
+            .is_err());
+        assert!(visualise(2024, 6, "....", Part::One, &[], 1, sink())
+            .unwrap()
+            .is_err());
+        assert!(visualise(
+            2024,
+            6,
+            DAY06,
+            Part::One,
+            &["size=1".parse().unwrap()],
+            1,
+            sink()
+        )
+        .unwrap()
+        .is_err());
+        assert!(visualise(2024, 26, DAY06, Part::One, &[], 1, sink()).is_none());
+    }
+}
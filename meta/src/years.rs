@@ -0,0 +1,6 @@
+//! Years added with `meta new`.
+//!
+//! Generated from the `aocYYYY/dayNN` crates of the workspace, `meta new`
+//! rewrites this file whenever it adds a day.
+
+registry!();
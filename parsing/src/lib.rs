@@ -0,0 +1,152 @@
+//! Error reporting for the puzzle input parsers.
+//!
+//! All helpers point at a subslice of the original input and turn it into a
+//! `line, column` location, so errors can be reported relative to the input
+//! file instead of to whatever piece of it a parser was looking at.
+
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+use eyre::eyre;
+use nom::IResult;
+
+/// A 1-based line and column in the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Location {
+    /// Location of the byte at `offset` in `input`.
+    pub fn at_offset(input: &str, offset: usize) -> Self {
+        let before = &input.as_bytes()[..offset.min(input.len())];
+        let line_start = before
+            .iter()
+            .rposition(|&c| c == b'\n')
+            .map_or(0, |idx| idx + 1);
+        Location {
+            line: before.iter().filter(|&&c| c == b'\n').count() + 1,
+            column: String::from_utf8_lossy(&before[line_start..])
+                .chars()
+                .count()
+                + 1,
+        }
+    }
+
+    /// Location of the start of `part`, which has to be a subslice of `input`.
+    ///
+    /// Slices that do not point into `input` are reported at its end.
+    pub fn of(input: &str, part: &str) -> Self {
+        let offset = (part.as_ptr() as usize).wrapping_sub(input.as_ptr() as usize);
+        Self::at_offset(input, offset)
+    }
+}
+
+impl Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+/// The beginning of `rest` for error messages, cut at the end of the line.
+fn snippet(rest: &str) -> String {
+    let line = rest.lines().next().unwrap_or_default();
+    match line.char_indices().nth(20) {
+        _ if line.is_empty() => "end of line".to_string(),
+        Some((idx, _)) => format!("{:?}...", &line[..idx]),
+        None => format!("{line:?}"),
+    }
+}
+
+/// An error located at the start of `part`, a subslice of `input`.
+pub fn error_at(input: &str, part: &str, message: impl Display) -> eyre::Report {
+    eyre!("{}: {message}", Location::of(input, part))
+}
+
+/// Parses `field`, a subslice of `input`, and reports failures at its location.
+pub fn parse_at<T>(input: &str, field: &str) -> Result<T, eyre::Report>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    field
+        .parse()
+        .map_err(|e| error_at(input, field, format!("invalid value {field:?}: {e}")))
+}
+
+/// Turns the result of a nom parser run on all of `input` into a located error.
+///
+/// Trailing whitespace is accepted, any other unconsumed input is an error.
+pub fn finish<'a, T>(input: &'a str, result: IResult<&'a str, T>) -> Result<T, eyre::Report> {
+    match result {
+        Ok((rest, _)) if !rest.trim().is_empty() => {
+            let rest = rest.trim_start();
+            Err(error_at(
+                input,
+                rest,
+                format!("unexpected trailing input {}", snippet(rest)),
+            ))
+        }
+        Ok((_, value)) => Ok(value),
+        Err(nom::Err::Error(e) | nom::Err::Failure(e)) => Err(error_at(
+            input,
+            e.input,
+            format!(
+                "expected {}, found {}",
+                e.code.description(),
+                snippet(e.input)
+            ),
+        )),
+        Err(nom::Err::Incomplete(_)) => Err(error_at(
+            input,
+            &input[input.len()..],
+            "unexpected end of input",
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::{bytes::complete::tag, character::complete::digit1, sequence::preceded};
+
+    use crate::{finish, parse_at, Location};
+
+    const TEST_INPUT: &str = "a: 1\nb: 22\nc: x3\n";
+
+    #[test]
+    fn test_location() {
+        assert_eq!(
+            Location::at_offset(TEST_INPUT, 0),
+            Location { line: 1, column: 1 }
+        );
+        assert_eq!(
+            Location::at_offset(TEST_INPUT, 8),
+            Location { line: 2, column: 4 }
+        );
+        let x = &TEST_INPUT[TEST_INPUT.find('x').unwrap()..];
+        assert_eq!(Location::of(TEST_INPUT, x).to_string(), "line 3, column 4");
+    }
+
+    #[test]
+    fn test_errors() {
+        let line = TEST_INPUT.lines().nth(2).unwrap();
+        let err = parse_at::<u32>(TEST_INPUT, &line[3..]).unwrap_err();
+        assert!(err.to_string().starts_with("line 3, column 4: "));
+
+        let number = |s| preceded(tag("a: "), digit1::<&str, _>)(s);
+        assert_eq!(finish("a: 12\n", number("a: 12\n")).unwrap(), "12");
+        let err = finish(TEST_INPUT, number(TEST_INPUT)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 1: unexpected trailing input \"b: 22\""
+        );
+        let err = finish("a: x", number("a: x")).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 4: expected Digit, found \"x\""
+        );
+    }
+}
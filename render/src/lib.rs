@@ -0,0 +1,426 @@
+//! Pictures and animations of grid frames.
+//!
+//! The days emit the state of a simulation as a sequence of [`Grid`] frames
+//! and a [`FrameSink`] turns them into text or images. Tile bytes are used as
+//! palette indices directly, so the image formats never need to convert a
+//! frame to RGB.
+
+use std::{
+    borrow::Cow,
+    fs::File,
+    io::{self, BufWriter, IsTerminal, Write},
+    path::PathBuf,
+    str::FromStr,
+    time::Duration,
+};
+
+use eyre::{bail, ensure, eyre, WrapErr};
+use grid::Grid;
+
+/// An RGB colour.
+pub type Color = [u8; 3];
+
+/// The colour of every possible tile byte.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Palette {
+    colors: [Color; 256],
+}
+
+/// Dark floor, grey walls and bright colours for the markers the days use.
+/// Tiles without a colour of their own are magenta so they stand out.
+impl Default for Palette {
+    fn default() -> Self {
+        let mut palette = Palette {
+            colors: [[255, 0, 255]; 256],
+        };
+        for (tiles, color) in [
+            (".", [20, 20, 30]),
+            ("#", [110, 110, 120]),
+            ("O[]", [200, 140, 60]),
+            ("@^>v<", [255, 220, 0]),
+            ("S", [0, 200, 80]),
+            ("E", [230, 40, 40]),
+            ("X", [70, 130, 220]),
+            ("o", [120, 200, 255]),
+            ("*", [255, 255, 255]),
+        ] {
+            for tile in tiles.bytes() {
+                palette.set(tile, color);
+            }
+        }
+        palette
+    }
+}
+
+impl Palette {
+    pub fn set(&mut self, tile: u8, color: Color) {
+        self.colors[tile as usize] = color;
+    }
+
+    pub fn color(&self, tile: u8) -> Color {
+        self.colors[tile as usize]
+    }
+
+    /// All colours as consecutive RGB bytes, indexed by tile.
+    fn rgb(&self) -> Vec<u8> {
+        self.colors.iter().flatten().copied().collect()
+    }
+}
+
+/// Overrides on top of the default palette, e.g. `#=404040,.=000000`.
+impl FromStr for Palette {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut palette = Palette::default();
+        for entry in s.split(',').filter(|entry| !entry.is_empty()) {
+            let (tile, color) = entry
+                .split_once('=')
+                .ok_or_else(|| eyre!("expected `tile=rrggbb`, got {entry:?}"))?;
+            let &[tile] = tile.as_bytes() else {
+                bail!("expected a single character tile, got {tile:?}");
+            };
+            ensure!(
+                color.len() == 6 && color.is_ascii(),
+                "expected a colour like ff8000, got {color:?}"
+            );
+            let channel = |idx: usize| {
+                u8::from_str_radix(&color[idx * 2..idx * 2 + 2], 16)
+                    .wrap_err_with(|| format!("invalid colour {color:?}"))
+            };
+            palette.set(tile, [channel(0)?, channel(1)?, channel(2)?]);
+        }
+        Ok(palette)
+    }
+}
+
+/// The tiles of `grid` as palette indices, every tile blown up to `scale` by
+/// `scale` pixels.
+fn pixels(grid: &Grid, scale: usize) -> (usize, usize, Vec<u8>) {
+    let (width, height) = (grid.width() * scale, grid.height() * scale);
+    let mut pixels = Vec::with_capacity(width * height);
+    for row in grid.cells().chunks(grid.width()) {
+        let start = pixels.len();
+        pixels.extend(
+            row.iter()
+                .flat_map(|&tile| std::iter::repeat_n(tile, scale)),
+        );
+        for _ in 1..scale {
+            pixels.extend_from_within(start..start + width);
+        }
+    }
+    (width, height, pixels)
+}
+
+/// Writes `grid` as a single PNG image.
+pub fn write_png<W: Write>(
+    out: W,
+    grid: &Grid,
+    palette: &Palette,
+    scale: usize,
+) -> Result<(), eyre::Report> {
+    let (width, height, pixels) = pixels(grid, scale);
+    let mut encoder = png::Encoder::new(out, width.try_into()?, height.try_into()?);
+    encoder.set_color(png::ColorType::Indexed);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.set_palette(palette.rgb());
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&pixels)?;
+    writer.finish()?;
+    Ok(())
+}
+
+/// Receives the frames of a single run.
+pub trait FrameSink {
+    fn frame(&mut self, grid: &Grid) -> Result<(), eyre::Report>;
+
+    /// Writes everything that is still buffered, called after the last frame.
+    fn finish(self: Box<Self>) -> Result<(), eyre::Report>;
+}
+
+/// Prints every frame as text, followed by an empty line.
+///
+/// When writing to a terminal the screen is cleared before every frame and
+/// each frame stays visible for `delay`, which plays the run as an animation.
+pub struct AsciiFrames<W> {
+    out: W,
+    delay: Duration,
+    animate: bool,
+}
+
+impl AsciiFrames<io::Stdout> {
+    pub fn stdout(delay: Duration) -> Self {
+        let out = io::stdout();
+        let animate = out.is_terminal();
+        AsciiFrames {
+            out,
+            delay,
+            animate,
+        }
+    }
+}
+
+impl<W: Write> AsciiFrames<W> {
+    pub fn new(out: W) -> Self {
+        AsciiFrames {
+            out,
+            delay: Duration::ZERO,
+            animate: false,
+        }
+    }
+}
+
+impl<W: Write> FrameSink for AsciiFrames<W> {
+    fn frame(&mut self, grid: &Grid) -> Result<(), eyre::Report> {
+        if self.animate {
+            write!(self.out, "\x1b[H\x1b[2J{grid}")?;
+            self.out.flush()?;
+            std::thread::sleep(self.delay);
+        } else {
+            writeln!(self.out, "{grid}")?;
+        }
+        Ok(())
+    }
+
+    fn finish(mut self: Box<Self>) -> Result<(), eyre::Report> {
+        self.out.flush()?;
+        Ok(())
+    }
+}
+
+/// Writes every frame to its own numbered PNG file in a directory.
+pub struct PngFrames {
+    dir: PathBuf,
+    palette: Palette,
+    scale: usize,
+    count: usize,
+}
+
+impl PngFrames {
+    /// Creates `dir` if it does not exist yet.
+    pub fn new(dir: PathBuf, palette: Palette, scale: usize) -> Result<Self, eyre::Report> {
+        std::fs::create_dir_all(&dir)
+            .wrap_err_with(|| format!("cannot create {}", dir.display()))?;
+        Ok(PngFrames {
+            dir,
+            palette,
+            scale,
+            count: 0,
+        })
+    }
+}
+
+impl FrameSink for PngFrames {
+    fn frame(&mut self, grid: &Grid) -> Result<(), eyre::Report> {
+        let path = self.dir.join(format!("frame{:05}.png", self.count));
+        let file =
+            File::create(&path).wrap_err_with(|| format!("cannot create {}", path.display()))?;
+        write_png(BufWriter::new(file), grid, &self.palette, self.scale)?;
+        self.count += 1;
+        Ok(())
+    }
+
+    fn finish(self: Box<Self>) -> Result<(), eyre::Report> {
+        Ok(())
+    }
+}
+
+/// Fails unless `grid` has the same size as the frames before it.
+fn check_size(size: &mut Option<(usize, usize)>, grid: &Grid) -> Result<(), eyre::Report> {
+    let current = (grid.width(), grid.height());
+    let first = *size.get_or_insert(current);
+    ensure!(
+        first == current,
+        "all frames of an animation need the same size, got {current:?} after {first:?}"
+    );
+    Ok(())
+}
+
+/// Streams the frames into an endlessly looping GIF.
+pub struct GifFrames<W: Write> {
+    out: Option<W>,
+    encoder: Option<gif::Encoder<W>>,
+    size: Option<(usize, usize)>,
+    palette: Palette,
+    scale: usize,
+    delay: Duration,
+}
+
+impl<W: Write> GifFrames<W> {
+    pub fn new(out: W, palette: Palette, scale: usize, delay: Duration) -> Self {
+        GifFrames {
+            out: Some(out),
+            encoder: None,
+            size: None,
+            palette,
+            scale,
+            delay,
+        }
+    }
+}
+
+impl<W: Write> FrameSink for GifFrames<W> {
+    fn frame(&mut self, grid: &Grid) -> Result<(), eyre::Report> {
+        check_size(&mut self.size, grid)?;
+        let (width, height, pixels) = pixels(grid, self.scale);
+        let (width, height) = (
+            u16::try_from(width).wrap_err("frame too wide for a GIF")?,
+            u16::try_from(height).wrap_err("frame too high for a GIF")?,
+        );
+        let encoder = match &mut self.encoder {
+            Some(encoder) => encoder,
+            None => {
+                let out = self.out.take().expect("the output is only taken once");
+                let mut encoder = gif::Encoder::new(out, width, height, &self.palette.rgb())?;
+                encoder.set_repeat(gif::Repeat::Infinite)?;
+                self.encoder.insert(encoder)
+            }
+        };
+        let frame = gif::Frame {
+            width,
+            height,
+            buffer: Cow::Owned(pixels),
+            // GIF delays are in hundredths of a second
+            delay: (self.delay.as_millis() / 10).try_into().unwrap_or(u16::MAX),
+            ..gif::Frame::default()
+        };
+        encoder.write_frame(&frame)?;
+        Ok(())
+    }
+
+    fn finish(self: Box<Self>) -> Result<(), eyre::Report> {
+        match self.encoder {
+            Some(encoder) => encoder.into_inner()?.flush()?,
+            None => bail!("no frames to write"),
+        }
+        Ok(())
+    }
+}
+
+/// Collects the frames and writes them as an endlessly looping APNG.
+///
+/// APNG stores the number of frames in its header, so all frames are kept in
+/// memory until [`FrameSink::finish`].
+pub struct ApngFrames<W> {
+    out: W,
+    frames: Vec<Vec<u8>>,
+    size: Option<(usize, usize)>,
+    palette: Palette,
+    scale: usize,
+    delay: Duration,
+}
+
+impl<W: Write> ApngFrames<W> {
+    pub fn new(out: W, palette: Palette, scale: usize, delay: Duration) -> Self {
+        ApngFrames {
+            out,
+            frames: Vec::new(),
+            size: None,
+            palette,
+            scale,
+            delay,
+        }
+    }
+}
+
+impl<W: Write> FrameSink for ApngFrames<W> {
+    fn frame(&mut self, grid: &Grid) -> Result<(), eyre::Report> {
+        check_size(&mut self.size, grid)?;
+        self.frames.push(pixels(grid, self.scale).2);
+        Ok(())
+    }
+
+    fn finish(self: Box<Self>) -> Result<(), eyre::Report> {
+        let Some((width, height)) = self.size else {
+            bail!("no frames to write");
+        };
+        let (width, height) = (width * self.scale, height * self.scale);
+        let mut encoder = png::Encoder::new(self.out, width.try_into()?, height.try_into()?);
+        encoder.set_color(png::ColorType::Indexed);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.set_palette(self.palette.rgb());
+        encoder.set_animated(self.frames.len().try_into()?, 0)?;
+        let delay = self.delay.as_millis().try_into().unwrap_or(u16::MAX);
+        encoder.set_frame_delay(delay, 1000)?;
+        let mut writer = encoder.write_header()?;
+        for frame in &self.frames {
+            writer.write_image_data(frame)?;
+        }
+        writer.finish()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use grid::Grid;
+
+    use crate::{pixels, write_png, ApngFrames, AsciiFrames, FrameSink, GifFrames, Palette};
+
+    #[test]
+    fn test_palette() {
+        let palette: Palette = "#=102030,.=ffffff".parse().unwrap();
+        assert_eq!(palette.color(b'#'), [0x10, 0x20, 0x30]);
+        assert_eq!(palette.color(b'.'), [255, 255, 255]);
+        assert_eq!(palette.color(b'S'), Palette::default().color(b'S'));
+        assert!("#=10203".parse::<Palette>().is_err());
+        assert!("##=102030".parse::<Palette>().is_err());
+        assert!("#102030".parse::<Palette>().is_err());
+        assert!("#=ééé".parse::<Palette>().is_err());
+    }
+
+    #[test]
+    fn test_pixels() {
+        let grid: Grid = "#.\n.S\n".parse().unwrap();
+        assert_eq!(pixels(&grid, 1), (2, 2, b"#..S".to_vec()));
+        assert_eq!(pixels(&grid, 2), (4, 4, b"##..##....SS..SS".to_vec()));
+    }
+
+    #[test]
+    fn test_images() {
+        let grid: Grid = "#.\n.S\n".parse().unwrap();
+        let mut out = Vec::new();
+        write_png(&mut out, &grid, &Palette::default(), 3).unwrap();
+        let png = png::Decoder::new(out.as_slice()).read_info().unwrap();
+        assert_eq!((png.info().width, png.info().height), (6, 6));
+
+        let mut out = Vec::new();
+        let mut apng = Box::new(ApngFrames::new(
+            &mut out,
+            Palette::default(),
+            1,
+            Duration::ZERO,
+        ));
+        apng.frame(&grid).unwrap();
+        apng.frame(&grid).unwrap();
+        assert!(apng.frame(&"#\n".parse().unwrap()).is_err());
+        apng.finish().unwrap();
+        let png = png::Decoder::new(out.as_slice()).read_info().unwrap();
+        assert_eq!(png.info().animation_control().unwrap().num_frames, 2);
+
+        let mut out = Vec::new();
+        let mut gif = Box::new(GifFrames::new(
+            &mut out,
+            Palette::default(),
+            1,
+            Duration::ZERO,
+        ));
+        gif.frame(&grid).unwrap();
+        gif.frame(&grid).unwrap();
+        gif.finish().unwrap();
+        let mut gif = gif::DecodeOptions::new().read_info(out.as_slice()).unwrap();
+        let mut frames = 0;
+        while gif.read_next_frame().unwrap().is_some() {
+            frames += 1;
+        }
+        assert_eq!(frames, 2);
+
+        let mut out = Vec::new();
+        let mut ascii = Box::new(AsciiFrames::new(&mut out));
+        ascii.frame(&grid).unwrap();
+        ascii.finish().unwrap();
+        assert_eq!(out, b"#.\n.S\n\n");
+    }
+}